@@ -0,0 +1,637 @@
+//! Generate AsciiDoc documentation for clap command-line tools.
+//!
+//! The AsciiDoc documents the same content as the Markdown generated by this
+//! crate. Each command is a section whose level reflects its subcommand
+//! nesting depth, with an explicit id used to cross-reference it from other
+//! sections. Usage lines are `[source,shell]` blocks, and arguments are
+//! documented using labeled lists.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use clap::builder::StyledStr;
+
+use crate::{
+    arg_details, arg_display_name, arg_group_rules, command_aliases,
+    command_anchors, command_arg_sections, command_usage, documented_commands,
+    emphasized_spans, get_canonical_name, global_options_anchor,
+    global_parents, inherited_globals_anchor, AnchorStyle, GlobalArg,
+};
+
+//======================================
+// Public API types
+//======================================
+
+/// Options to customize the structure of the output AsciiDoc document.
+///
+/// Used with [`help_asciidoc_custom()`].
+#[non_exhaustive]
+pub struct AsciiDocOptions {
+    title: Option<String>,
+    show_footer: bool,
+    show_table_of_contents: bool,
+}
+
+impl AsciiDocOptions {
+    /// Construct a default instance of `AsciiDocOptions`.
+    pub fn new() -> Self {
+        return Self {
+            title: None,
+            show_footer: true,
+            show_table_of_contents: true,
+        };
+    }
+
+    /// Set a custom title to use in the generated document.
+    pub fn title(mut self, title: String) -> Self {
+        self.title = Some(title);
+
+        return self;
+    }
+
+    /// Whether to show the default footer advertising `clap-markdown`.
+    pub fn show_footer(mut self, show: bool) -> Self {
+        self.show_footer = show;
+
+        return self;
+    }
+
+    /// Whether to show the default table of contents.
+    pub fn show_table_of_contents(mut self, show: bool) -> Self {
+        self.show_table_of_contents = show;
+
+        return self;
+    }
+}
+
+impl Default for AsciiDocOptions {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+//======================================
+// Public API functions
+//======================================
+
+/// Format the help information for `command` as AsciiDoc.
+pub fn help_asciidoc<C: clap::CommandFactory>() -> String {
+    let command = C::command();
+
+    return help_asciidoc_command(&command);
+}
+
+/// Format the help information for `command` as AsciiDoc, with custom
+/// options.
+pub fn help_asciidoc_custom<C: clap::CommandFactory>(
+    options: &AsciiDocOptions,
+) -> String {
+    let command = C::command();
+
+    return help_asciidoc_command_custom(&command, options);
+}
+
+/// Format the help information for `command` as AsciiDoc.
+pub fn help_asciidoc_command(command: &clap::Command) -> String {
+    return help_asciidoc_command_custom(command, &Default::default());
+}
+
+/// Format the help information for `command` as AsciiDoc, with custom
+/// options.
+pub fn help_asciidoc_command_custom(
+    command: &clap::Command,
+    options: &AsciiDocOptions,
+) -> String {
+    let mut buffer = String::with_capacity(100);
+
+    write_help_asciidoc(&mut buffer, command, options)
+        .expect("writing to a String should never fail");
+
+    return buffer;
+}
+
+//======================================
+// AsciiDoc
+//======================================
+
+fn write_help_asciidoc(
+    buffer: &mut dyn Write,
+    command: &clap::Command,
+    options: &AsciiDocOptions,
+) -> fmt::Result {
+    // The ids of command sections are set explicitly, so use the explicit
+    // anchor style, e.g. `tool-remote-add`.
+    let anchors = command_anchors(command, AnchorStyle::Explicit);
+
+    let title_name = get_canonical_name(command);
+
+    //----------------------------------
+    // Write the document title
+    //----------------------------------
+
+    match options.title {
+        Some(ref title) => writeln!(buffer, "= {title}\n")?,
+        None => writeln!(
+            buffer,
+            "= Command-Line Help for {}\n",
+            literal(&title_name)
+        )?,
+    }
+
+    writeln!(
+        buffer,
+        "This document contains the help content for the {} command-line program.\n",
+        literal(&title_name)
+    )?;
+
+    //----------------------------------
+    // Write the table of contents
+    //----------------------------------
+
+    if options.show_table_of_contents {
+        writeln!(buffer, "*Command Overview:*\n")?;
+
+        build_table_of_contents_asciidoc(buffer, &anchors, command)?;
+
+        writeln!(buffer)?;
+    }
+
+    //----------------------------------------
+    // Write the commands/subcommands sections
+    //----------------------------------------
+
+    build_command_asciidoc(buffer, &anchors, command)?;
+
+    //----------------------------------
+    // Write the footer
+    //----------------------------------
+
+    if options.show_footer {
+        write!(
+            buffer,
+            r#"'''
+
+_This document was generated automatically by https://crates.io/crates/clap-markdown[`clap-markdown`]._
+"#
+        )?;
+    }
+
+    Ok(())
+}
+
+fn build_table_of_contents_asciidoc(
+    buffer: &mut dyn Write,
+    anchors: &HashMap<Vec<String>, String>,
+    command: &clap::Command,
+) -> fmt::Result {
+    for node in documented_commands(command) {
+        writeln!(
+            buffer,
+            "* <<{},{}>>",
+            anchors[&node.command_path],
+            literal(&node.command_path.join(" "))
+        )?;
+    }
+
+    Ok(())
+}
+
+fn build_command_asciidoc(
+    buffer: &mut dyn Write,
+    anchors: &HashMap<Vec<String>, String>,
+    command: &clap::Command,
+) -> fmt::Result {
+    for node in documented_commands(command) {
+        write_command_section_asciidoc(
+            buffer,
+            anchors,
+            node.parent_command_path(),
+            &node.inherited_globals,
+            node.command,
+            node.depth(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Write the section documenting `command`, not including its subcommands.
+fn write_command_section_asciidoc(
+    buffer: &mut dyn Write,
+    anchors: &HashMap<Vec<String>, String>,
+    // Parent commands of `command`.
+    parent_command_path: &[String],
+    // Global arguments declared by the parent commands of `command`.
+    inherited_globals: &[GlobalArg],
+    command: &clap::Command,
+    depth: usize,
+) -> fmt::Result {
+    // Append the name of `command` to `command_path`.
+    let command_path = {
+        let mut command_path = parent_command_path.to_vec();
+        command_path.push(get_canonical_name(command));
+        command_path
+    };
+
+    //----------------------------------
+    // Write the heading
+    //----------------------------------
+
+    // The root command is a level 1 section (`==`), and each level of
+    // subcommand nesting adds one `=`. AsciiDoc has no section level deeper
+    // than 5 (`======`).
+    let section_level = usize::min(depth + 1, 5);
+
+    writeln!(buffer, "[#{}]", anchors[&command_path])?;
+    writeln!(
+        buffer,
+        "{} {}\n",
+        "=".repeat(section_level + 1),
+        literal(&command_path.join(" "))
+    )?;
+
+    if let Some(about) = command.get_long_about().or(command.get_about()) {
+        writeln!(buffer, "{}\n", styled_asciidoc(command, about).join("\n\n"))?;
+    }
+
+    if let Some(help) =
+        command.get_before_long_help().or(command.get_before_help())
+    {
+        writeln!(buffer, "{}\n", styled_asciidoc(command, help).join("\n\n"))?;
+    }
+
+    writeln!(buffer, ".Usage")?;
+    writeln!(buffer, "[source,shell]")?;
+    writeln!(
+        buffer,
+        "----\n{}\n----\n",
+        command_usage(parent_command_path, command)
+    )?;
+
+    let aliases = command_aliases(command);
+
+    if !aliases.is_empty() {
+        writeln!(
+            buffer,
+            "*Command {}:* {}\n",
+            if aliases.len() > 1 {
+                "Aliases"
+            } else {
+                "Alias"
+            },
+            literal_list(&aliases)
+        )?;
+    }
+
+    if let Some(help) =
+        command.get_after_long_help().or(command.get_after_help())
+    {
+        writeln!(buffer, "{}\n", styled_asciidoc(command, help).join("\n\n"))?;
+    }
+
+    //----------------------------------
+    // Subcommands
+    //----------------------------------
+
+    let subcommands: Vec<&clap::Command> = command
+        .get_subcommands()
+        .filter(|subcommand| !subcommand.is_hide_set())
+        .collect();
+
+    if !subcommands.is_empty() {
+        writeln!(buffer, ".Subcommands")?;
+
+        for subcommand in subcommands {
+            let title_name = get_canonical_name(subcommand);
+
+            let mut subcommand_path = command_path.clone();
+            subcommand_path.push(title_name.clone());
+
+            write!(
+                buffer,
+                "<<{},{}>>",
+                anchors[&subcommand_path],
+                literal(&title_name)
+            )?;
+
+            let aliases = command_aliases(subcommand);
+
+            if !aliases.is_empty() {
+                write!(
+                    buffer,
+                    " ({}: {})",
+                    if aliases.len() > 1 {
+                        "aliases"
+                    } else {
+                        "alias"
+                    },
+                    literal_list(&aliases)
+                )?;
+            }
+
+            match subcommand.get_about() {
+                Some(about) => writeln!(
+                    buffer,
+                    ":: {}",
+                    styled_asciidoc(subcommand, about).join("\n+\n")
+                )?,
+                None => writeln!(buffer, "::")?,
+            }
+        }
+
+        writeln!(buffer)?;
+    }
+
+    //----------------------------------
+    // Arguments
+    //----------------------------------
+
+    for section in command_arg_sections(command, inherited_globals) {
+        if section.is_global_options {
+            writeln!(
+                buffer,
+                "[#{}]",
+                global_options_anchor(&anchors[&command_path])
+            )?;
+        }
+
+        writeln!(buffer, ".{}", escape_asciidoc(section.heading))?;
+
+        for arg in section.args {
+            write_arg_asciidoc(buffer, command, arg)?;
+        }
+
+        writeln!(buffer)?;
+    }
+
+    // Cross-reference the inherited global options using `<<id,text>>`.
+    for parent_path in global_parents(inherited_globals) {
+        let anchor =
+            inherited_globals_anchor(anchors, inherited_globals, parent_path);
+
+        writeln!(
+            buffer,
+            "This command also accepts the <<{anchor},global options>> of {}.\n",
+            literal(&parent_path.join(" "))
+        )?;
+    }
+
+    //----------------------------------
+    // Argument groups
+    //----------------------------------
+
+    let groups = arg_group_rules(command);
+
+    if !groups.is_empty() {
+        writeln!(buffer, ".Argument Groups")?;
+
+        for group in groups {
+            let members: Vec<String> =
+                group.members.into_iter().map(arg_display_name).collect();
+
+            writeln!(
+                buffer,
+                "* {} — {} {} {} be given",
+                literal(group.id),
+                group.rule,
+                literal_list(&members),
+                group.verb,
+            )?;
+        }
+
+        writeln!(buffer)?;
+    }
+
+    Ok(())
+}
+
+/// Write the documentation of `arg` as an item of a labeled list.
+fn write_arg_asciidoc(
+    buffer: &mut dyn Write,
+    command: &clap::Command,
+    arg: &clap::Arg,
+) -> fmt::Result {
+    let details = arg_details(command, arg);
+
+    //--------------------
+    // Arg names
+    //--------------------
+
+    write!(buffer, "{}", literal_list(&details.names()))?;
+
+    if !details.notes.is_empty() {
+        write!(buffer, " ({})", details.notes.join(", "))?;
+    }
+
+    writeln!(buffer, "::")?;
+
+    // The blocks documenting the argument, attached to the list item using
+    // `+` list continuations.
+    let mut blocks: Vec<String> = Vec::new();
+
+    //--------------------
+    // Arg help
+    //--------------------
+
+    if let Some(help) = details.help {
+        blocks.extend(styled_asciidoc(command, help));
+    }
+
+    //--------------------
+    // Arg aliases
+    //--------------------
+
+    if !details.aliases.is_empty() {
+        blocks.push(format!(
+            "{}: {}",
+            if details.aliases.len() > 1 {
+                "Aliases"
+            } else {
+                "Alias"
+            },
+            literal_list(&details.aliases)
+        ));
+    }
+
+    //--------------------
+    // Arg conflicts
+    //--------------------
+
+    if details.exclusive {
+        blocks.push("Cannot be used with any other arguments.".to_owned());
+    } else if !details.conflicts.is_empty() {
+        blocks.push(format!(
+            "Cannot be used with {}.",
+            literal_list(&details.conflicts)
+        ));
+    }
+
+    //--------------------
+    // Arg environment variable
+    //--------------------
+
+    if let Some(env) = &details.env {
+        blocks.push(format!("Environment variable: {}", literal(env)));
+    }
+
+    //--------------------
+    // Arg default values
+    //--------------------
+
+    if !details.default_values.is_empty() {
+        blocks.push(format!(
+            "Default {}: {}",
+            if details.default_values.len() > 1 {
+                "values"
+            } else {
+                "value"
+            },
+            literal_list(&details.default_values)
+        ));
+    }
+
+    //--------------------
+    // Arg possible values
+    //--------------------
+
+    let possible_values = &details.possible_values;
+
+    if possible_values.iter().any(|pv| pv.get_help().is_some()) {
+        blocks.push("Possible values:".to_owned());
+
+        let items: Vec<String> = possible_values
+            .iter()
+            .map(|pv| match pv.get_help() {
+                Some(help) => format!(
+                    "* {}: {}",
+                    literal(pv.get_name()),
+                    styled_asciidoc(command, help).join(" ")
+                ),
+                None => format!("* {}", literal(pv.get_name())),
+            })
+            .collect();
+
+        blocks.push(items.join("\n"));
+    } else if !possible_values.is_empty() {
+        let names: Vec<String> = possible_values
+            .iter()
+            .map(|pv| pv.get_name().to_owned())
+            .collect();
+
+        blocks.push(format!("Possible values: {}", literal_list(&names)));
+    }
+
+    if !blocks.is_empty() {
+        writeln!(buffer, "{}", blocks.join("\n+\n"))?;
+    }
+
+    Ok(())
+}
+
+//======================================
+// Utilities
+//======================================
+
+/// Convert styled help text of `command` to AsciiDoc paragraphs, splitting the
+/// text into paragraphs at blank lines.
+///
+/// Literal text is shown as monospace, and strong and emphasized text use bold
+/// and italic formatting. Other text is [escaped](escape_asciidoc), so it is
+/// shown as is.
+fn styled_asciidoc(command: &clap::Command, text: &StyledStr) -> Vec<String> {
+    let mut asciidoc = String::new();
+
+    for (emphasis, text) in emphasized_spans(command, text) {
+        // Formatting marks can't span a paragraph break, so mark up each line.
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 {
+                asciidoc.push('\n');
+            }
+
+            let content = line.trim();
+
+            if content.is_empty() {
+                asciidoc.push_str(line);
+                continue;
+            }
+
+            // Unconstrained formatting marks are used, since styled text may
+            // start or end in the middle of a word.
+            let mut styled = if emphasis.literal {
+                literal(content)
+            } else {
+                escape_asciidoc(content)
+            };
+            if emphasis.emphasis {
+                styled = format!("__{styled}__");
+            }
+            if emphasis.strong {
+                styled = format!("**{styled}**");
+            }
+
+            let start = line.len() - line.trim_start().len();
+
+            asciidoc.push_str(&line[..start]);
+            asciidoc.push_str(&styled);
+            asciidoc.push_str(&line[start + content.len()..]);
+        }
+    }
+
+    let mut paragraphs = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+
+    for line in asciidoc.lines().chain([""]) {
+        if line.trim().is_empty() {
+            if !paragraph.is_empty() {
+                paragraphs.push(paragraph.join("\n"));
+                paragraph.clear();
+            }
+        } else {
+            paragraph.push(line);
+        }
+    }
+
+    return paragraphs;
+}
+
+/// Escape `text`, so that AsciiDoc shows it as is, instead of interpreting
+/// characters like `*`, `_`, `{`, and `<<` as formatting, attribute
+/// references, or cross references.
+///
+/// AsciiDoc has no backslash escape that works for every character in every
+/// position, so text containing any of these characters is wrapped in an
+/// inline passthrough that only escapes HTML special characters, e.g.
+/// `pass:c[*.rs]`.
+fn escape_asciidoc(text: &str) -> String {
+    let is_markup = |c: char| {
+        matches!(
+            c,
+            '*' | '_' | '`' | '+' | '#' | '^' | '~' | '{' | '[' | '\\'
+        )
+    };
+
+    if !text.contains(is_markup) && !text.contains("<<") {
+        return text.to_owned();
+    }
+
+    return format!("pass:c[{}]", text.replace(']', "\\]"));
+}
+
+/// Format `text` as literal monospace text, e.g. `` `+--foo <BAR>+` ``.
+///
+/// The `+` passthrough prevents the text from being interpreted as AsciiDoc
+/// markup.
+fn literal(text: &str) -> String {
+    return format!("`+{text}+`");
+}
+
+/// Format `items` as a comma-separated list of literal monospace text.
+fn literal_list(items: &[String]) -> String {
+    return items
+        .iter()
+        .map(|item| literal(item))
+        .collect::<Vec<String>>()
+        .join(", ");
+}
@@ -0,0 +1,722 @@
+//! Generate standalone HTML documentation for clap command-line tools.
+//!
+//! The HTML documents the same content as the Markdown generated by this
+//! crate, using semantic elements: command headings with `id` attributes,
+//! `<code>` usage lines, and `<dl>` definition lists for subcommands and
+//! arguments.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use clap::builder::StyledStr;
+
+use crate::{
+    arg_details, arg_display_name, arg_group_rules, command_aliases,
+    command_anchors, command_arg_sections, command_usage, documented_commands,
+    emphasized_spans, get_canonical_name, global_options_anchor,
+    global_parents, inherited_globals_anchor, AnchorStyle, GlobalArg,
+};
+
+/// The stylesheet embedded in generated HTML documents, unless a custom
+/// stylesheet is set using [`HtmlOptions::stylesheet()`].
+pub const DEFAULT_HTML_STYLESHEET: &str = r#"body {
+    font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif;
+    line-height: 1.5;
+    max-width: 50em;
+    margin: 0 auto;
+    padding: 1em;
+    color: #1f2328;
+}
+
+code {
+    font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace;
+    font-size: 0.9em;
+    background: #f6f8fa;
+    border-radius: 4px;
+    padding: 0.1em 0.3em;
+}
+
+h2 {
+    margin-top: 2em;
+    padding-bottom: 0.3em;
+    border-bottom: 1px solid #d1d9e0;
+}
+
+h3 {
+    font-size: 1em;
+}
+
+dt {
+    margin-top: 0.75em;
+}
+
+dd > p {
+    margin: 0.25em 0;
+}
+
+footer {
+    margin-top: 3em;
+    color: #59636e;
+}
+"#;
+
+//======================================
+// Public API types
+//======================================
+
+/// Options to customize the structure of the output HTML document.
+///
+/// Used with [`help_html_custom()`].
+#[non_exhaustive]
+pub struct HtmlOptions {
+    title: Option<String>,
+    show_footer: bool,
+    show_table_of_contents: bool,
+    embed_stylesheet: bool,
+    stylesheet: Option<String>,
+}
+
+impl HtmlOptions {
+    /// Construct a default instance of `HtmlOptions`.
+    pub fn new() -> Self {
+        return Self {
+            title: None,
+            show_footer: true,
+            show_table_of_contents: true,
+            embed_stylesheet: true,
+            stylesheet: None,
+        };
+    }
+
+    /// Set a custom title to use in the generated document.
+    pub fn title(mut self, title: String) -> Self {
+        self.title = Some(title);
+
+        return self;
+    }
+
+    /// Whether to show the default footer advertising `clap-markdown`.
+    pub fn show_footer(mut self, show: bool) -> Self {
+        self.show_footer = show;
+
+        return self;
+    }
+
+    /// Whether to show the default table of contents.
+    pub fn show_table_of_contents(mut self, show: bool) -> Self {
+        self.show_table_of_contents = show;
+
+        return self;
+    }
+
+    /// Whether to embed a stylesheet in a `<style>` element in the generated
+    /// document.
+    ///
+    /// Disable this to style the document using your own stylesheet.
+    pub fn embed_stylesheet(mut self, embed: bool) -> Self {
+        self.embed_stylesheet = embed;
+
+        return self;
+    }
+
+    /// Set the CSS stylesheet to embed in the generated document, instead of
+    /// [`DEFAULT_HTML_STYLESHEET`].
+    pub fn stylesheet(mut self, css: String) -> Self {
+        self.stylesheet = Some(css);
+
+        return self;
+    }
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+//======================================
+// Public API functions
+//======================================
+
+/// Format the help information for `command` as a standalone HTML document.
+pub fn help_html<C: clap::CommandFactory>() -> String {
+    let command = C::command();
+
+    return help_html_command(&command);
+}
+
+/// Format the help information for `command` as a standalone HTML document,
+/// with custom options.
+pub fn help_html_custom<C: clap::CommandFactory>(
+    options: &HtmlOptions,
+) -> String {
+    let command = C::command();
+
+    return help_html_command_custom(&command, options);
+}
+
+/// Format the help information for `command` as a standalone HTML document.
+pub fn help_html_command(command: &clap::Command) -> String {
+    return help_html_command_custom(command, &Default::default());
+}
+
+/// Format the help information for `command` as a standalone HTML document,
+/// with custom options.
+pub fn help_html_command_custom(
+    command: &clap::Command,
+    options: &HtmlOptions,
+) -> String {
+    let mut buffer = String::with_capacity(100);
+
+    write_help_html(&mut buffer, command, options)
+        .expect("writing to a String should never fail");
+
+    return buffer;
+}
+
+//======================================
+// HTML
+//======================================
+
+fn write_help_html(
+    buffer: &mut dyn Write,
+    command: &clap::Command,
+    options: &HtmlOptions,
+) -> fmt::Result {
+    // The ids of command headings are set explicitly, so use the explicit
+    // anchor style, e.g. `tool-remote-add`.
+    let anchors = command_anchors(command, AnchorStyle::Explicit);
+
+    let title_name = get_canonical_name(command);
+
+    //----------------------------------
+    // Write the document head
+    //----------------------------------
+
+    let (title_text, title_html) = match options.title {
+        Some(ref title) => (title.clone(), escape_html(title)),
+        None => (
+            format!("Command-Line Help for {title_name}"),
+            format!(
+                "Command-Line Help for <code>{}</code>",
+                escape_html(&title_name)
+            ),
+        ),
+    };
+
+    writeln!(buffer, "<!DOCTYPE html>")?;
+    writeln!(buffer, "<html lang=\"en\">")?;
+    writeln!(buffer, "<head>")?;
+    writeln!(buffer, "<meta charset=\"utf-8\">")?;
+    writeln!(buffer, "<title>{}</title>", escape_html(&title_text))?;
+
+    if options.embed_stylesheet {
+        let stylesheet = options
+            .stylesheet
+            .as_deref()
+            .unwrap_or(DEFAULT_HTML_STYLESHEET);
+
+        writeln!(buffer, "<style>\n{}\n</style>", stylesheet.trim_end())?;
+    }
+
+    writeln!(buffer, "</head>")?;
+    writeln!(buffer, "<body>")?;
+
+    //----------------------------------
+    // Write the document title
+    //----------------------------------
+
+    writeln!(buffer, "<h1>{title_html}</h1>")?;
+
+    writeln!(
+        buffer,
+        "<p>This document contains the help content for the <code>{}</code> command-line program.</p>",
+        escape_html(&title_name)
+    )?;
+
+    //----------------------------------
+    // Write the table of contents
+    //----------------------------------
+
+    if options.show_table_of_contents {
+        writeln!(buffer, "<nav>")?;
+        writeln!(buffer, "<p><strong>Command Overview:</strong></p>")?;
+        writeln!(buffer, "<ul>")?;
+        build_table_of_contents_html(buffer, &anchors, command)?;
+        writeln!(buffer, "</ul>")?;
+        writeln!(buffer, "</nav>")?;
+    }
+
+    //----------------------------------------
+    // Write the commands/subcommands sections
+    //----------------------------------------
+
+    build_command_html(buffer, &anchors, command)?;
+
+    //----------------------------------
+    // Write the footer
+    //----------------------------------
+
+    if options.show_footer {
+        write!(
+            buffer,
+            r#"<footer>
+<hr>
+<p><small><i>
+    This document was generated automatically by
+    <a href="https://crates.io/crates/clap-markdown"><code>clap-markdown</code></a>.
+</i></small></p>
+</footer>
+"#
+        )?;
+    }
+
+    writeln!(buffer, "</body>")?;
+    writeln!(buffer, "</html>")?;
+
+    Ok(())
+}
+
+fn build_table_of_contents_html(
+    buffer: &mut dyn Write,
+    anchors: &HashMap<Vec<String>, String>,
+    command: &clap::Command,
+) -> fmt::Result {
+    for node in documented_commands(command) {
+        writeln!(
+            buffer,
+            "<li><a href=\"#{}\"><code>{}</code></a></li>",
+            escape_html(&anchors[&node.command_path]),
+            escape_html(&node.command_path.join(" "))
+        )?;
+    }
+
+    Ok(())
+}
+
+fn build_command_html(
+    buffer: &mut dyn Write,
+    anchors: &HashMap<Vec<String>, String>,
+    command: &clap::Command,
+) -> fmt::Result {
+    for node in documented_commands(command) {
+        write_command_section_html(
+            buffer,
+            anchors,
+            node.parent_command_path(),
+            &node.inherited_globals,
+            node.command,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Write the heading and help content for `command`, not including its
+/// subcommands.
+fn write_command_section_html(
+    buffer: &mut dyn Write,
+    anchors: &HashMap<Vec<String>, String>,
+    // Parent commands of `command`.
+    parent_command_path: &[String],
+    // Global arguments declared by the parent commands of `command`.
+    inherited_globals: &[GlobalArg],
+    command: &clap::Command,
+) -> fmt::Result {
+    // Append the name of `command` to `command_path`.
+    let command_path = {
+        let mut command_path = parent_command_path.to_vec();
+        command_path.push(get_canonical_name(command));
+        command_path
+    };
+
+    writeln!(buffer, "<section>")?;
+
+    //----------------------------------
+    // Write the heading
+    //----------------------------------
+
+    writeln!(
+        buffer,
+        "<h2 id=\"{}\"><code>{}</code></h2>",
+        escape_html(&anchors[&command_path]),
+        escape_html(&command_path.join(" "))
+    )?;
+
+    if let Some(long_about) = command.get_long_about() {
+        write!(buffer, "{}", styled_html_paragraphs(command, long_about))?;
+    } else if let Some(about) = command.get_about() {
+        write!(buffer, "{}", styled_html_paragraphs(command, about))?;
+    }
+
+    if let Some(help) = command.get_before_long_help() {
+        write!(buffer, "{}", styled_html_paragraphs(command, help))?;
+    } else if let Some(help) = command.get_before_help() {
+        write!(buffer, "{}", styled_html_paragraphs(command, help))?;
+    }
+
+    writeln!(
+        buffer,
+        "<p><strong>Usage:</strong> <code>{}</code></p>",
+        escape_html(&command_usage(parent_command_path, command))
+    )?;
+
+    let aliases = command_aliases(command);
+
+    if !aliases.is_empty() {
+        writeln!(
+            buffer,
+            "<p><strong>Command {}:</strong> {}</p>",
+            if aliases.len() > 1 {
+                "Aliases"
+            } else {
+                "Alias"
+            },
+            code_list_html(&aliases)
+        )?;
+    }
+
+    if let Some(help) = command.get_after_long_help() {
+        write!(buffer, "{}", styled_html_paragraphs(command, help))?;
+    } else if let Some(help) = command.get_after_help() {
+        write!(buffer, "{}", styled_html_paragraphs(command, help))?;
+    }
+
+    //----------------------------------
+    // Subcommands
+    //----------------------------------
+
+    let subcommands: Vec<&clap::Command> = command
+        .get_subcommands()
+        .filter(|subcommand| !subcommand.is_hide_set())
+        .collect();
+
+    if !subcommands.is_empty() {
+        writeln!(buffer, "<h3>Subcommands:</h3>")?;
+        writeln!(buffer, "<dl>")?;
+
+        for subcommand in subcommands {
+            let title_name = get_canonical_name(subcommand);
+
+            let mut subcommand_path = command_path.clone();
+            subcommand_path.push(title_name.clone());
+
+            write!(
+                buffer,
+                "<dt><a href=\"#{}\"><code>{}</code></a>",
+                escape_html(&anchors[&subcommand_path]),
+                escape_html(&title_name)
+            )?;
+
+            let aliases = command_aliases(subcommand);
+
+            if !aliases.is_empty() {
+                write!(
+                    buffer,
+                    " ({}: {})",
+                    if aliases.len() > 1 {
+                        "aliases"
+                    } else {
+                        "alias"
+                    },
+                    code_list_html(&aliases)
+                )?;
+            }
+
+            writeln!(buffer, "</dt>")?;
+
+            let about = match subcommand.get_about() {
+                Some(about) => styled_html(subcommand, about),
+                None => String::new(),
+            };
+
+            writeln!(buffer, "<dd>{about}</dd>")?;
+        }
+
+        writeln!(buffer, "</dl>")?;
+    }
+
+    //----------------------------------
+    // Arguments
+    //----------------------------------
+
+    for section in command_arg_sections(command, inherited_globals) {
+        if section.is_global_options {
+            writeln!(
+                buffer,
+                "<h3 id=\"{}\">{}:</h3>",
+                escape_html(&global_options_anchor(&anchors[&command_path])),
+                escape_html(section.heading)
+            )?;
+        } else {
+            writeln!(buffer, "<h3>{}:</h3>", escape_html(section.heading))?;
+        }
+        writeln!(buffer, "<dl>")?;
+
+        for arg in section.args {
+            write_arg_html(buffer, command, arg)?;
+        }
+
+        writeln!(buffer, "</dl>")?;
+    }
+
+    // Link to the inherited global options, within this page.
+    for parent_path in global_parents(inherited_globals) {
+        let anchor =
+            inherited_globals_anchor(anchors, inherited_globals, parent_path);
+
+        writeln!(
+            buffer,
+            "<p>This command also accepts the <a href=\"#{}\">global options</a> of <code>{}</code>.</p>",
+            escape_html(&anchor),
+            escape_html(&parent_path.join(" "))
+        )?;
+    }
+
+    //----------------------------------
+    // Argument groups
+    //----------------------------------
+
+    let groups = arg_group_rules(command);
+
+    if !groups.is_empty() {
+        writeln!(buffer, "<h3>Argument Groups:</h3>")?;
+        writeln!(buffer, "<ul>")?;
+
+        for group in groups {
+            let members: Vec<String> =
+                group.members.into_iter().map(arg_display_name).collect();
+
+            writeln!(
+                buffer,
+                "<li><code>{}</code> — {} {} {} be given</li>",
+                escape_html(group.id),
+                group.rule,
+                code_list_html(&members),
+                group.verb,
+            )?;
+        }
+
+        writeln!(buffer, "</ul>")?;
+    }
+
+    writeln!(buffer, "</section>")?;
+
+    Ok(())
+}
+
+fn write_arg_html(
+    buffer: &mut dyn Write,
+    command: &clap::Command,
+    arg: &clap::Arg,
+) -> fmt::Result {
+    let details = arg_details(command, arg);
+
+    //--------------------
+    // Arg names
+    //--------------------
+
+    write!(buffer, "<dt>{}", code_list_html(&details.names()))?;
+
+    if !details.notes.is_empty() {
+        write!(buffer, " ({})", details.notes.join(", "))?;
+    }
+
+    writeln!(buffer, "</dt>")?;
+
+    writeln!(buffer, "<dd>")?;
+
+    //--------------------
+    // Arg help
+    //--------------------
+
+    if let Some(help) = details.help {
+        write!(buffer, "{}", styled_html_paragraphs(command, help))?;
+    }
+
+    //--------------------
+    // Arg aliases
+    //--------------------
+
+    if !details.aliases.is_empty() {
+        writeln!(
+            buffer,
+            "<p>{}: {}</p>",
+            if details.aliases.len() > 1 {
+                "Aliases"
+            } else {
+                "Alias"
+            },
+            code_list_html(&details.aliases)
+        )?;
+    }
+
+    //--------------------
+    // Arg conflicts
+    //--------------------
+
+    if details.exclusive {
+        writeln!(buffer, "<p>Cannot be used with any other arguments.</p>")?;
+    } else if !details.conflicts.is_empty() {
+        writeln!(
+            buffer,
+            "<p>Cannot be used with {}.</p>",
+            code_list_html(&details.conflicts)
+        )?;
+    }
+
+    //--------------------
+    // Arg environment variable
+    //--------------------
+
+    if let Some(env) = &details.env {
+        writeln!(
+            buffer,
+            "<p>Environment variable: <code>{}</code></p>",
+            escape_html(env)
+        )?;
+    }
+
+    //--------------------
+    // Arg default values
+    //--------------------
+
+    if !details.default_values.is_empty() {
+        writeln!(
+            buffer,
+            "<p>Default {}: {}</p>",
+            if details.default_values.len() > 1 {
+                "values"
+            } else {
+                "value"
+            },
+            code_list_html(&details.default_values)
+        )?;
+    }
+
+    //--------------------
+    // Arg possible values
+    //--------------------
+
+    if !details.possible_values.is_empty() {
+        writeln!(buffer, "<p>Possible values:</p>")?;
+        writeln!(buffer, "<ul>")?;
+
+        for pv in &details.possible_values {
+            write!(buffer, "<li><code>{}</code>", escape_html(pv.get_name()))?;
+
+            if let Some(help) = pv.get_help() {
+                write!(buffer, ": {}", styled_html(command, help))?;
+            }
+
+            writeln!(buffer, "</li>")?;
+        }
+
+        writeln!(buffer, "</ul>")?;
+    }
+
+    writeln!(buffer, "</dd>")?;
+
+    Ok(())
+}
+
+//======================================
+// Utilities
+//======================================
+
+/// Convert styled help text of `command` to inline HTML.
+///
+/// Literal text is shown using `<code>`, and strong and emphasized text use
+/// `<strong>` and `<em>`.
+fn styled_html(command: &clap::Command, text: &StyledStr) -> String {
+    let mut html = String::new();
+
+    for (emphasis, text) in emphasized_spans(command, text) {
+        let mut open = String::new();
+        let mut close = String::new();
+
+        if emphasis.literal {
+            open.push_str("<code>");
+            close.insert_str(0, "</code>");
+        }
+        if emphasis.strong {
+            open.push_str("<strong>");
+            close.insert_str(0, "</strong>");
+        }
+        if emphasis.emphasis {
+            open.push_str("<em>");
+            close.insert_str(0, "</em>");
+        }
+
+        // Close the elements at the end of each line, so that no element
+        // spans a paragraph break.
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 {
+                html.push('\n');
+            }
+
+            if line.trim().is_empty() {
+                html.push_str(line);
+                continue;
+            }
+
+            html.push_str(&open);
+            html.push_str(&escape_html(line));
+            html.push_str(&close);
+        }
+    }
+
+    return html;
+}
+
+/// Convert styled help text of `command` to HTML paragraphs, splitting the
+/// text into paragraphs at blank lines.
+fn styled_html_paragraphs(command: &clap::Command, text: &StyledStr) -> String {
+    let html = styled_html(command, text);
+
+    let mut paragraphs = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+
+    for line in html.lines().chain([""]) {
+        if line.trim().is_empty() {
+            if !paragraph.is_empty() {
+                writeln!(paragraphs, "<p>{}</p>", paragraph.join("\n"))
+                    .expect("writing to a String should never fail");
+                paragraph.clear();
+            }
+        } else {
+            paragraph.push(line);
+        }
+    }
+
+    return paragraphs;
+}
+
+/// Format `items` as a comma-separated list of `<code>` elements.
+fn code_list_html(items: &[String]) -> String {
+    return items
+        .iter()
+        .map(|item| format!("<code>{}</code>", escape_html(item)))
+        .collect::<Vec<String>>()
+        .join(", ");
+}
+
+/// Escape characters in `text` that have a special meaning in HTML.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    return escaped;
+}
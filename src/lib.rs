@@ -5,6 +5,22 @@
 //!
 //! [Examples]: https://github.com/ConnorGray/clap-markdown#Examples
 //!
+//! # Argument relationships
+//!
+//! The documentation of an argument states which arguments it cannot be used
+//! with, based on `Arg::conflicts_with()`, `Arg::exclusive()`, and argument
+//! groups that do not allow `multiple` arguments. Argument groups that are
+//! required or do not allow `multiple` arguments are described in an
+//! "Argument Groups" section.
+//!
+//! clap does not provide public getters for the following rules, so they
+//! cannot be documented:
+//!
+//! * `Arg::requires()` and its variants (e.g. `Arg::requires_if()`)
+//! * `Arg::required_unless_present()` and its variants
+//! * `Arg::required_if_eq()` and its variants
+//! * `ArgGroup::requires()` and `ArgGroup::conflicts_with()`
+//!
 
 // Ensure that doc tests in the README.md file get run.
 #[doc(hidden)]
@@ -12,9 +28,37 @@ mod test_readme {
     #![doc = include_str!("../README.md")]
 }
 
-use std::fmt::{self, Write};
-
-use clap::builder::PossibleValue;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::{self, Write},
+    io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use clap::builder::{
+    styling::{Ansi256Color, Color, Effects, RgbColor, Style},
+    PossibleValue, StyledStr, ValueRange,
+};
+
+mod asciidoc;
+mod html;
+mod man;
+pub mod model;
+mod render;
+
+pub use asciidoc::{
+    help_asciidoc, help_asciidoc_command, help_asciidoc_command_custom,
+    help_asciidoc_custom, AsciiDocOptions,
+};
+pub use html::{
+    help_html, help_html_command, help_html_command_custom, help_html_custom,
+    HtmlOptions, DEFAULT_HTML_STYLESHEET,
+};
+pub use man::{
+    help_man_command_pages, help_man_command_pages_custom, help_man_pages,
+    help_man_pages_custom, ManOptions,
+};
+pub use render::{CommandContext, MarkdownRenderer, Renderer, TocEntry};
 
 //======================================
 // Public API types
@@ -28,6 +72,24 @@ pub struct MarkdownOptions {
     title: Option<String>,
     show_footer: bool,
     show_table_of_contents: bool,
+    nested_table_of_contents: bool,
+    table_of_contents_max_depth: Option<usize>,
+    nested_headings: bool,
+    heading_base_level: usize,
+    anchor_style: AnchorStyle,
+    show_aliases: bool,
+    env_vars_section: EnvVarsSection,
+    help_text_format: HelpTextFormat,
+    arg_layout: ArgLayout,
+    header_template: Option<String>,
+    command_template: Option<String>,
+    footer_template: Option<String>,
+    section_heading_template: String,
+    date: Option<String>,
+    front_matter: Option<String>,
+    flavor: MarkdownFlavor,
+    arg_admonition: Option<String>,
+    renderer: Option<Box<dyn Renderer + Send + Sync>>,
 }
 
 impl MarkdownOptions {
@@ -37,6 +99,24 @@ impl MarkdownOptions {
             title: None,
             show_footer: true,
             show_table_of_contents: true,
+            nested_table_of_contents: false,
+            table_of_contents_max_depth: None,
+            nested_headings: false,
+            heading_base_level: 2,
+            anchor_style: AnchorStyle::GitHub,
+            show_aliases: true,
+            env_vars_section: EnvVarsSection::Disabled,
+            help_text_format: HelpTextFormat::Markdown,
+            arg_layout: ArgLayout::List,
+            header_template: None,
+            command_template: None,
+            footer_template: None,
+            section_heading_template: "###### **{{heading}}:**".to_owned(),
+            date: None,
+            front_matter: None,
+            flavor: MarkdownFlavor::Standard,
+            arg_admonition: None,
+            renderer: None,
         };
     }
 
@@ -60,6 +140,353 @@ impl MarkdownOptions {
 
         return self;
     }
+
+    /// Whether to show the visible aliases of commands and arguments.
+    ///
+    /// Hidden aliases are never shown.
+    pub fn show_aliases(mut self, show: bool) -> Self {
+        self.show_aliases = show;
+
+        return self;
+    }
+
+    /// Whether to include a consolidated section listing the environment
+    /// variables bound to arguments.
+    ///
+    /// Regardless of this setting, the environment variable bound to an
+    /// argument is always shown in the documentation of that argument, unless
+    /// it has been hidden using `Arg::hide_env()`.
+    ///
+    /// Environment variables are only documented if the `env` feature of this
+    /// crate is enabled. It is not enabled by default, so that depending on
+    /// this crate does not enable the `env` feature of `clap`.
+    pub fn env_vars_section(mut self, section: EnvVarsSection) -> Self {
+        self.env_vars_section = section;
+
+        return self;
+    }
+
+    /// Set how the help text of commands, arguments, and possible values is
+    /// interpreted.
+    ///
+    /// The default is [`HelpTextFormat::Markdown`], which includes help text
+    /// in the document verbatim.
+    pub fn help_text_format(mut self, format: HelpTextFormat) -> Self {
+        self.help_text_format = format;
+
+        return self;
+    }
+
+    /// Set the flavor of Markdown to generate.
+    ///
+    /// The default is [`MarkdownFlavor::Standard`]. Use
+    /// [`MarkdownFlavor::Mdx`] for documents processed by MDX, e.g. by
+    /// Docusaurus.
+    pub fn flavor(mut self, flavor: MarkdownFlavor) -> Self {
+        self.flavor = flavor;
+
+        return self;
+    }
+
+    /// Wrap the list of arguments in each argument section (e.g. "Options")
+    /// in an admonition of the given kind, e.g. `note` or `info`.
+    ///
+    /// Admonitions are written using the `:::kind` container syntax supported
+    /// by Docusaurus and other static site generators.
+    pub fn arg_admonition(mut self, kind: String) -> Self {
+        self.arg_admonition = Some(kind);
+
+        return self;
+    }
+
+    /// Set how the arguments of each command are laid out.
+    ///
+    /// The default is [`ArgLayout::List`].
+    pub fn arg_layout(mut self, layout: ArgLayout) -> Self {
+        self.arg_layout = layout;
+
+        return self;
+    }
+
+    /// Whether the table of contents should be a nested list that reflects the
+    /// subcommand hierarchy.
+    ///
+    /// By default, the table of contents is a flat list showing the full path
+    /// of every command. When enabled, each entry is indented by its nesting
+    /// depth and shows only the name of the command.
+    pub fn nested_table_of_contents(mut self, nested: bool) -> Self {
+        self.nested_table_of_contents = nested;
+
+        return self;
+    }
+
+    /// Set the maximum subcommand nesting depth shown in the table of
+    /// contents.
+    ///
+    /// A depth of `0` shows only the root command, `1` also shows its direct
+    /// subcommands, and so on. By default, commands of any depth are shown.
+    ///
+    /// This does not affect which commands are documented in the body of the
+    /// document.
+    pub fn table_of_contents_max_depth(mut self, max_depth: usize) -> Self {
+        self.table_of_contents_max_depth = Some(max_depth);
+
+        return self;
+    }
+
+    /// Whether the heading level of each command section should reflect the
+    /// nesting depth of the command.
+    ///
+    /// By default, every command section uses the same heading level, so
+    /// `tool remote add` is shown as a peer of `tool`. When enabled, the
+    /// heading of the root command uses the
+    /// [base level](MarkdownOptions::heading_base_level), and each level of
+    /// subcommand nesting adds one `#` to the heading.
+    ///
+    /// Markdown only supports six heading levels. Commands nested deeper than
+    /// that are all given level six (`######`) headings.
+    pub fn nested_headings(mut self, nested: bool) -> Self {
+        self.nested_headings = nested;
+
+        return self;
+    }
+
+    /// Set the heading level used for the root command section.
+    ///
+    /// The default level is `2` (`##`), placing command sections one level
+    /// below the document title.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `level` is not in the range `1..=6`.
+    pub fn heading_base_level(mut self, level: usize) -> Self {
+        assert!(
+            (1..=6).contains(&level),
+            "Markdown heading level must be between 1 and 6, got: {level}"
+        );
+
+        self.heading_base_level = level;
+
+        return self;
+    }
+
+    /// Set the strategy used to generate the anchors that the table of contents
+    /// and other links use to refer to command sections.
+    ///
+    /// Markdown renderers differ in how they derive anchors from heading text,
+    /// so this should match the renderer used to display the document. The
+    /// default is [`AnchorStyle::GitHub`].
+    pub fn anchor_style(mut self, style: AnchorStyle) -> Self {
+        self.anchor_style = style;
+
+        return self;
+    }
+
+    /// Set a template for the YAML front matter shown at the top of the
+    /// document, as used by static site generators like Hugo and Docusaurus.
+    ///
+    /// The template is the content of the front matter, without the
+    /// surrounding `---` lines. [`DEFAULT_FRONT_MATTER`] is a template that
+    /// sets the `title`, `description`, and `weight` of the page.
+    ///
+    /// When generating a page for each command, each page gets its own front
+    /// matter, populated from the command documented by the page.
+    ///
+    /// See [`MarkdownOptions::header_template()`] for the template syntax.
+    /// Placeholders are replaced with quoted YAML strings, except for
+    /// `{{weight}}`, which is replaced with a number. The front matter
+    /// template supports these variables:
+    ///
+    /// * `{{title}}` — the document [title](MarkdownOptions::title) for the
+    ///   root command if set, and otherwise the command path, e.g.
+    ///   `tool remote add`
+    /// * `{{name}}` — the name of the command
+    /// * `{{command_path}}` — the names of the command and its parent
+    ///   commands, e.g. `tool remote add`
+    /// * `{{about}}` — the short description of the command, as plain text
+    /// * `{{version}}` — the version of the program
+    /// * `{{date}}` — the [generation date](MarkdownOptions::date)
+    /// * `{{weight}}` — the position of the page, starting from `1`, when
+    ///   the pages are ordered like the table of contents
+    /// * `{{slug}}` — the path of the page generated by
+    ///   [`help_markdown_pages()`], without the `.md` extension, e.g.
+    ///   `tool/remote/add`, or `index` for the root command. The default
+    ///   template doesn't set the slug, so that the URL of each page follows
+    ///   its path, which the links between pages rely on
+    pub fn front_matter(mut self, template: String) -> Self {
+        self.front_matter = Some(template);
+
+        return self;
+    }
+
+    /// Set the generation date substituted for `{{date}}` placeholders, e.g.
+    /// `2024-06-15`.
+    ///
+    /// By default, the date the document is generated on (in UTC) is used,
+    /// formatted as `YYYY-MM-DD`. If the `SOURCE_DATE_EPOCH` environment
+    /// variable is set, the date it refers to is used instead, for
+    /// [reproducible builds](https://reproducible-builds.org/docs/source-date-epoch/).
+    pub fn date(mut self, date: String) -> Self {
+        self.date = Some(date);
+
+        return self;
+    }
+
+    /// Set a template for the document header, replacing the default title,
+    /// introduction, and table of contents.
+    ///
+    /// Templates are Markdown containing `{{variable}}` placeholders, which
+    /// are replaced with the value of the variable. Placeholders of unknown
+    /// variables are left unchanged. The header template supports these
+    /// variables:
+    ///
+    /// * `{{title}}` — the document [title](MarkdownOptions::title)
+    /// * `{{name}}` — the name of the program
+    /// * `{{version}}` — the version of the program
+    /// * `{{date}}` — the [generation date](MarkdownOptions::date)
+    /// * `{{about}}` — the description of the program
+    /// * `{{table_of_contents}}` — the table of contents list, or nothing if
+    ///   the [table of contents](MarkdownOptions::show_table_of_contents) is
+    ///   disabled
+    /// * `{{default_header}}` — the default header, e.g. to add a "generated
+    ///   file — do not edit" banner above it:
+    ///
+    /// ```markdown
+    /// > Generated from `{{name}}` v{{version}} on {{date}} — do not edit.
+    ///
+    /// {{default_header}}
+    /// ```
+    ///
+    /// When generating a page for each command, the header is shown at the
+    /// top of the index page.
+    ///
+    /// For example, this template reproduces the default header:
+    ///
+    /// ```markdown
+    /// # {{title}}
+    ///
+    /// This document contains the help content for the `{{name}}` command-line program.
+    ///
+    /// **Command Overview:**
+    ///
+    /// {{table_of_contents}}
+    /// ```
+    pub fn header_template(mut self, template: String) -> Self {
+        self.header_template = Some(template);
+
+        return self;
+    }
+
+    /// Set a template for the section documenting each command, replacing
+    /// the default layout of the section.
+    ///
+    /// See [`MarkdownOptions::header_template()`] for the template syntax. The
+    /// command template supports these variables:
+    ///
+    /// * `{{heading}}` — the heading of the section, e.g. ``## `tool remote` ``,
+    ///   as written by [`Renderer::write_command_heading()`]
+    /// * `{{command_path}}` — the names of the command and its parent
+    ///   commands, e.g. `tool remote`
+    /// * `{{name}}` — the name of the command
+    /// * `{{version}}` — the version of the command
+    /// * `{{about}}` — the description of the command
+    /// * `{{before_help}}` and `{{after_help}}` — the help text shown before
+    ///   and after the arguments of the command
+    /// * `{{usage}}` — the usage of the command, e.g.
+    ///   `tool remote [OPTIONS] <NAME>`
+    /// * `{{aliases}}` — the visible aliases of the command
+    /// * `{{subcommands}}`, `{{arguments}}`, `{{arg_groups}}`, and
+    ///   `{{env_vars}}` — the sections documenting the subcommands, arguments,
+    ///   argument groups, and environment variables of the command, including
+    ///   their headings
+    ///
+    /// Variables that don't apply to a command are empty.
+    pub fn command_template(mut self, template: String) -> Self {
+        self.command_template = Some(template);
+
+        return self;
+    }
+
+    /// Set a template for the document footer, replacing the default footer
+    /// advertising `clap-markdown`.
+    ///
+    /// See [`MarkdownOptions::header_template()`] for the template syntax. The
+    /// footer template supports the `{{name}}`, `{{version}}`, and `{{date}}`
+    /// variables, and `{{default_footer}}`, the default footer, e.g. to add a
+    /// license notice after it:
+    ///
+    /// ```markdown
+    /// {{default_footer}}
+    ///
+    /// Licensed under the MIT license.
+    /// ```
+    ///
+    /// The footer is only shown if [`MarkdownOptions::show_footer()`] is
+    /// enabled. When generating a page for each command, the footer is shown
+    /// at the end of every page.
+    pub fn footer_template(mut self, template: String) -> Self {
+        self.footer_template = Some(template);
+
+        return self;
+    }
+
+    /// Set the template for the headings of the subsections of each command
+    /// section, like "Options" and "Subcommands".
+    ///
+    /// The template supports the `{{heading}}` variable, the name of the
+    /// subsection. The default template is `###### **{{heading}}:**`.
+    ///
+    /// See [`MarkdownOptions::header_template()`] for the template syntax.
+    pub fn section_heading_template(mut self, template: String) -> Self {
+        self.section_heading_template = template;
+
+        return self;
+    }
+
+    /// Set a custom [`Renderer`] used to write the pieces of the document.
+    ///
+    /// The default is [`MarkdownRenderer`].
+    pub fn renderer<R: Renderer + Send + Sync + 'static>(
+        mut self,
+        renderer: R,
+    ) -> Self {
+        self.renderer = Some(Box::new(renderer));
+
+        return self;
+    }
+
+    /// Get the renderer used to write the pieces of the document.
+    fn active_renderer(&self) -> &dyn Renderer {
+        match self.renderer {
+            Some(ref renderer) => renderer.as_ref(),
+            None => &MarkdownRenderer,
+        }
+    }
+
+    /// Get the heading of a subsection of a command section, e.g. "Options".
+    fn section_heading(&self, heading: &str) -> String {
+        // Headings can be set by `help_heading()`, so may contain anything.
+        let heading = match self.flavor {
+            MarkdownFlavor::Standard => heading.to_owned(),
+            MarkdownFlavor::Mdx => escape_mdx(heading),
+        };
+
+        return render_template(
+            &self.section_heading_template,
+            &[("heading", &heading)],
+        );
+    }
+
+    /// Get the heading level to use for a command nested `depth` subcommands
+    /// deep.
+    fn command_heading_level(&self, depth: usize) -> usize {
+        if !self.nested_headings {
+            return self.heading_base_level;
+        }
+
+        // Markdown has no heading level deeper than `######`.
+        return usize::min(self.heading_base_level + depth, 6);
+    }
 }
 
 impl Default for MarkdownOptions {
@@ -68,6 +495,155 @@ impl Default for MarkdownOptions {
     }
 }
 
+/// A [front matter template](MarkdownOptions::front_matter) that sets the
+/// `title`, `description`, and `weight` of the page.
+pub const DEFAULT_FRONT_MATTER: &str = "\
+title: {{title}}
+description: {{about}}
+weight: {{weight}}
+";
+
+/// Where to include a consolidated section listing the environment variables
+/// bound to arguments.
+///
+/// Used with [`MarkdownOptions::env_vars_section()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EnvVarsSection {
+    /// Don't include an environment variables section.
+    Disabled,
+    /// Include an environment variables section in the documentation of each
+    /// command, listing the variables used by the arguments of that command.
+    PerCommand,
+    /// Include a single environment variables section at the end of the
+    /// document, listing the variables used by the arguments of every command.
+    Document,
+}
+
+/// How the help text of commands, arguments, and possible values is
+/// interpreted.
+///
+/// Used with [`MarkdownOptions::help_text_format()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HelpTextFormat {
+    /// Help text is already Markdown, and is included in the document
+    /// verbatim.
+    ///
+    /// Markdown written in help text, like `**bold**` or `[links](...)`, is
+    /// rendered, but so is any text that happens to look like Markdown, like
+    /// `*.rs`.
+    Markdown,
+    /// Help text is plain text. Characters that have a special meaning in
+    /// Markdown or HTML (like `*`, `_`, and `<`) are escaped, so that the text
+    /// is rendered as it is shown in the terminal.
+    PlainText,
+}
+
+/// The flavor of Markdown to generate.
+///
+/// Used with [`MarkdownOptions::flavor()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MarkdownFlavor {
+    /// CommonMark, with GitHub-flavored Markdown extensions like tables, and
+    /// inline HTML.
+    Standard,
+    /// Markdown that can be processed by [MDX](https://mdxjs.com/), which
+    /// treats `<` and `{` as the start of JSX and JavaScript expressions.
+    ///
+    /// `<`, `{`, and `}` in help text, section headings, and the document
+    /// title are escaped (outside of code spans), and only JSX-compatible HTML
+    /// is generated, like self-closing `<br/>` elements.
+    Mdx,
+}
+
+/// How the arguments of each command are laid out.
+///
+/// Used with [`MarkdownOptions::arg_layout()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArgLayout {
+    /// Document each argument as an item of a bulleted list, followed by its
+    /// help text and details.
+    List,
+    /// Document the arguments of each section as a GitHub-flavored Markdown
+    /// table, with columns for the flags, value, default value, environment
+    /// variable, and description of each argument.
+    ///
+    /// Multi-line help text and the other details of an argument are joined
+    /// into the description cell using `<br>` line breaks.
+    Table,
+}
+
+/// Strategy used to generate the anchor of each command section heading.
+///
+/// If two commands would be given the same anchor, a numeric suffix (`-1`,
+/// `-2`, ...) is appended to the later anchors, matching how Markdown
+/// renderers deduplicate heading ids.
+///
+/// Used with [`MarkdownOptions::anchor_style()`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum AnchorStyle {
+    /// Match the heading anchors generated by GitHub.
+    ///
+    /// Text is lowercased, punctuation other than `-` and `_` is removed, and
+    /// each space is replaced with a `-`.
+    GitHub,
+    /// Match the heading anchors generated by GitLab.
+    ///
+    /// Like [`AnchorStyle::GitHub`], but runs of consecutive `-` are collapsed
+    /// into a single `-`.
+    GitLab,
+    /// Match the heading anchors generated by mdBook.
+    ///
+    /// Text is lowercased, characters other than alphanumerics, `-` and `_`
+    /// are removed, and each whitespace character is replaced with a `-`.
+    MdBook,
+    /// Emit an explicit `<a id="..."></a>` element before each command
+    /// heading, instead of relying on the renderer to generate heading ids.
+    ///
+    /// The id is the command path joined with `-`, e.g. `tool-remote-add`.
+    Explicit,
+    /// Use a custom function to generate the anchor from the text of the
+    /// command heading, e.g. `tool remote add`.
+    ///
+    /// The headings are assumed to be given these ids by the renderer; use
+    /// [`AnchorStyle::Explicit`] to have the ids set explicitly.
+    Custom(fn(&str) -> String),
+}
+
+impl AnchorStyle {
+    /// Get the (not yet deduplicated) anchor for the command at
+    /// `command_path`.
+    fn slug(&self, command_path: &[String]) -> String {
+        // The text content of the command heading, without the surrounding
+        // backticks.
+        let heading_text = command_path.join(" ");
+
+        match self {
+            AnchorStyle::GitHub => github_slug(&heading_text),
+            AnchorStyle::GitLab => github_slug(&heading_text)
+                .split('-')
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("-"),
+            AnchorStyle::MdBook => heading_text
+                .chars()
+                .filter_map(|c| match c {
+                    _ if c.is_whitespace() => Some('-'),
+                    '-' | '_' => Some(c),
+                    _ if c.is_alphanumeric() => Some(c.to_ascii_lowercase()),
+                    _ => None,
+                })
+                .collect(),
+            AnchorStyle::Explicit => command_path.join("-"),
+            AnchorStyle::Custom(slugify) => slugify(&heading_text),
+        }
+    }
+}
+
 //======================================
 // Public API functions
 //======================================
@@ -100,464 +676,2392 @@ pub fn help_markdown_command_custom(
 ) -> String {
     let mut buffer = String::with_capacity(100);
 
-    write_help_markdown(&mut buffer, &command, options);
+    write_help_markdown_fmt(&mut buffer, command, options)
+        .expect("writing to a String should never fail");
 
     buffer
 }
 
-//======================================
-// Markdown
-//======================================
+/// Write the help information for `command` as Markdown to `writer`, with
+/// custom options.
+///
+/// The Markdown is streamed to `writer` as it is generated, instead of first
+/// being collected into a [`String`]. Writing is not buffered, so for file or
+/// socket output consider wrapping `writer` in a [`std::io::BufWriter`].
+///
+/// Any error returned by `writer` is propagated to the caller.
+pub fn write_help_markdown_to<W: io::Write + ?Sized>(
+    writer: &mut W,
+    command: &clap::Command,
+    options: &MarkdownOptions,
+) -> io::Result<()> {
+    let mut adapter = IoWriter {
+        inner: writer,
+        error: None,
+    };
 
-/// Format the help information for `command` as Markdown and print it.
+    match write_help_markdown(&mut adapter, command, options) {
+        Ok(()) => Ok(()),
+        Err(fmt::Error) => Err(adapter
+            .error
+            .unwrap_or_else(|| io::Error::other("formatter error"))),
+    }
+}
+
+/// Write the help information for `command` as Markdown to `writer`, with
+/// custom options.
 ///
-/// Output is printed to the standard output, using [`println!`].
-pub fn print_help_markdown<C: clap::CommandFactory>() {
-    let command = C::command();
+/// Like [`write_help_markdown_to()`], but writes to a [`std::fmt::Write`]
+/// sink, like a [`String`] or a [`std::fmt::Formatter`].
+pub fn write_help_markdown_fmt<W: fmt::Write + ?Sized>(
+    writer: &mut W,
+    command: &clap::Command,
+    options: &MarkdownOptions,
+) -> fmt::Result {
+    let mut writer = FmtWriter(writer);
 
-    let mut buffer = String::with_capacity(100);
+    write_help_markdown(&mut writer, command, options)
+}
 
-    write_help_markdown(&mut buffer, &command, &Default::default());
+/// Format the help information for `command` as a separate Markdown page for
+/// each command, with custom options.
+///
+/// See [`help_markdown_command_pages()`].
+pub fn help_markdown_pages<C: clap::CommandFactory>(
+    options: &MarkdownOptions,
+) -> BTreeMap<String, String> {
+    let command = C::command();
 
-    println!("{}", buffer);
+    return help_markdown_command_pages(&command, options);
 }
 
-fn write_help_markdown(
-    buffer: &mut String,
+/// Format the help information for `command` as a separate Markdown page for
+/// each command, with custom options.
+///
+/// The returned map contains the content of each page, keyed by the
+/// `/`-separated path of the page, relative to the directory the pages are
+/// written to.
+///
+/// The root command is documented by an `index.md` page, which also contains
+/// the document title and table of contents. Each subcommand is documented on
+/// a page whose path reflects the subcommand hierarchy, e.g. `tool remote add`
+/// is documented by `tool/remote/add.md`.
+///
+/// The table of contents and subcommand lists link to the page of each
+/// command.
+pub fn help_markdown_command_pages(
     command: &clap::Command,
     options: &MarkdownOptions,
-) {
-    //----------------------------------
-    // Write the document title
-    //----------------------------------
+) -> BTreeMap<String, String> {
+    let mut pages = BTreeMap::new();
 
-    let title_name = get_canonical_name(command);
+    build_markdown_pages(&mut pages, options, &document_date(options), command)
+        .expect("writing to a String should never fail");
 
-    let title = match options.title {
-        Some(ref title) => title.to_owned(),
-        None => format!("Command-Line Help for `{title_name}`"),
-    };
-    writeln!(buffer, "# {title}\n",).unwrap();
+    pages
+}
 
-    writeln!(
-        buffer,
-        "This document contains the help content for the `{}` command-line program.\n",
-        title_name
-    ).unwrap();
+/// Format an [mdBook] `SUMMARY.md` fragment listing the pages generated by
+/// [`help_markdown_pages()`].
+///
+/// See [`help_markdown_command_summary()`].
+///
+/// [mdBook]: https://rust-lang.github.io/mdBook/format/summary.html
+pub fn help_markdown_summary<C: clap::CommandFactory>(
+    pages_dir: &str,
+) -> String {
+    let command = C::command();
 
-    //----------------------------------
-    // Write the table of contents
-    //----------------------------------
+    return help_markdown_command_summary(&command, pages_dir);
+}
 
-    // writeln!(buffer, r#"<div style="background: light-gray"><ul>"#).unwrap();
-    // build_table_of_contents_html(buffer, Vec::new(), command, 0).unwrap();
-    // writeln!(buffer, "</ul></div>").unwrap();
+/// Format an [mdBook] `SUMMARY.md` fragment listing the pages generated by
+/// [`help_markdown_command_pages()`].
+///
+/// The fragment is a nested list with an entry for each documented command,
+/// mirroring the subcommand hierarchy. Hidden commands are omitted, as they are
+/// from the table of contents.
+///
+/// `pages_dir` is the directory the pages were written to, relative to the
+/// directory containing `SUMMARY.md`. Use `""` if the pages are in the same
+/// directory as `SUMMARY.md`.
+///
+/// [mdBook]: https://rust-lang.github.io/mdBook/format/summary.html
+pub fn help_markdown_command_summary(
+    command: &clap::Command,
+    pages_dir: &str,
+) -> String {
+    let mut buffer = String::with_capacity(100);
 
-    if options.show_table_of_contents {
-        writeln!(buffer, "**Command Overview:**\n").unwrap();
+    build_summary_markdown(&mut buffer, pages_dir, command)
+        .expect("writing to a String should never fail");
 
-        build_table_of_contents_markdown(buffer, Vec::new(), command, 0)
-            .unwrap();
+    buffer
+}
 
-        write!(buffer, "\n").unwrap();
-    }
+/// Build the documentation model of `command`.
+///
+/// See [`help_model_command()`].
+pub fn help_model<C: clap::CommandFactory>() -> model::CommandDoc {
+    let command = C::command();
 
-    //----------------------------------------
-    // Write the commands/subcommands sections
-    //----------------------------------------
+    return help_model_command(&command);
+}
 
-    build_command_markdown(buffer, Vec::new(), command, 0).unwrap();
+/// Build the documentation model of `command`.
+///
+/// The model contains the content that would be included in the Markdown
+/// documentation of `command` and its subcommands, as plain data. It can be
+/// used to generate documentation in formats not supported by this crate.
+///
+/// Help text is converted to plain text, without any styling.
+pub fn help_model_command(command: &clap::Command) -> model::CommandDoc {
+    return model::build_command_doc(&[], &[], command);
+}
 
-    //-----------------
-    // Write the footer
-    //-----------------
-    if options.show_footer {
-        write!(buffer, r#"<hr/>
+/// Format the documentation model of `command` as JSON.
+///
+/// See [`help_json_command()`].
+#[cfg(feature = "serde")]
+pub fn help_json<C: clap::CommandFactory>() -> String {
+    let command = C::command();
 
-<small><i>
-    This document was generated automatically by
-    <a href="https://crates.io/crates/clap-markdown"><code>clap-markdown</code></a>.
-</i></small>
-"#).unwrap();
-    }
+    return help_json_command(&command);
 }
 
-fn build_table_of_contents_markdown(
-    buffer: &mut String,
-    // Parent commands of `command`.
-    parent_command_path: Vec<String>,
+/// Format the documentation model of `command` as pretty-printed JSON.
+///
+/// The JSON is the serialization of the [`model::CommandDoc`] returned by
+/// [`help_model_command()`]. To export the model in another format, like YAML,
+/// serialize the model directly using the corresponding `serde` crate.
+#[cfg(feature = "serde")]
+pub fn help_json_command(command: &clap::Command) -> String {
+    let model = help_model_command(command);
+
+    return serde_json::to_string_pretty(&model)
+        .expect("serializing the documentation model should never fail");
+}
+
+//======================================
+// Markdown
+//======================================
+
+/// Format the help information for `command` as Markdown and print it.
+///
+/// Output is streamed to the standard output using
+/// [`write_help_markdown_to()`].
+///
+/// # Panics
+///
+/// Panics if writing to the standard output fails, like [`println!`].
+pub fn print_help_markdown<C: clap::CommandFactory>() {
+    let command = C::command();
+
+    let mut stdout = io::stdout().lock();
+
+    write_help_markdown_to(&mut stdout, &command, &Default::default())
+        // Match the trailing newline historically added by `println!`.
+        .and_then(|()| io::Write::write_all(&mut stdout, b"\n"))
+        .expect("failed printing Markdown help to stdout");
+}
+
+fn write_help_markdown(
+    buffer: &mut dyn Write,
     command: &clap::Command,
-    depth: usize,
-) -> std::fmt::Result {
-    // Don't document commands marked with `clap(hide = true)` (which includes
-    // `print-all-help`).
-    if command.is_hide_set() {
-        return Ok(());
-    }
+    options: &MarkdownOptions,
+) -> fmt::Result {
+    let anchors = command_anchors(command, options.anchor_style);
+    let links = CommandLinks::Anchor(&anchors);
+    let date = document_date(options);
 
-    let title_name = get_canonical_name(command);
+    write_front_matter(buffer, options, &date, command, &[], command, 1)?;
 
-    // Append the name of `command` to `command_path`.
-    let command_path = {
-        let mut command_path = parent_command_path;
-        command_path.push(title_name);
-        command_path
-    };
+    write_document_header(buffer, command, options, &date, links)?;
 
-    writeln!(
-        buffer,
-        "* [`{}`↴](#{})",
-        command_path.join(" "),
-        command_path.join("-"),
-    )?;
+    //----------------------------------------
+    // Write the commands/subcommands sections
+    //----------------------------------------
 
-    //----------------------------------
-    // Recurse to write subcommands
-    //----------------------------------
+    build_command_markdown(buffer, options, links, command)?;
 
-    for subcommand in command.get_subcommands() {
-        build_table_of_contents_markdown(
-            buffer,
-            command_path.clone(),
-            subcommand,
-            depth + 1,
-        )?;
+    if options.env_vars_section == EnvVarsSection::Document {
+        write_document_env_vars_markdown(buffer, options, command)?;
     }
 
+    write_footer(buffer, options, &date, command)?;
+
     Ok(())
 }
 
-/*
-fn build_table_of_contents_html(
-    buffer: &mut String,
-    // Parent commands of `command`.
-    parent_command_path: Vec<String>,
+/// Write the document header: by default, the document title, introduction,
+/// and table of contents.
+fn write_document_header(
+    buffer: &mut dyn Write,
     command: &clap::Command,
-    depth: usize,
-) -> std::fmt::Result {
-    // Don't document commands marked with `clap(hide = true)` (which includes
-    // `print-all-help`).
-    if command.is_hide_set() {
-        return Ok(());
-    }
+    options: &MarkdownOptions,
+    // The generation date of the document.
+    date: &str,
+    links: CommandLinks,
+) -> fmt::Result {
+    let Some(ref template) = options.header_template else {
+        return write_default_document_header(buffer, command, options, links);
+    };
 
-    // Append the name of `command` to `command_path`.
-    let command_path = {
-        let mut command_path = parent_command_path;
-        command_path.push(command.get_name().to_owned());
-        command_path
+    let table_of_contents = if options.show_table_of_contents {
+        write_to_string(|buffer| {
+            build_table_of_contents_markdown(buffer, options, links, command)
+        })?
+    } else {
+        String::new()
     };
 
-    writeln!(
-        buffer,
-        "<li><a href=\"#{}\"><code>{}</code>↴</a></li>",
-        command_path.join("-"),
-        command_path.join(" ")
-    )?;
+    let default_header = write_to_string(|buffer| {
+        write_default_document_header(buffer, command, options, links)
+    })?;
 
-    //----------------------------------
-    // Recurse to write subcommands
-    //----------------------------------
+    let header = render_document_template(
+        template,
+        date,
+        command,
+        &[
+            ("title", &document_title(options, command)),
+            ("about", &command_about(options, command)),
+            ("table_of_contents", &table_of_contents),
+            ("default_header", default_header.trim_end()),
+        ],
+    );
 
-    for subcommand in command.get_subcommands() {
-        build_table_of_contents_html(
-            buffer,
-            command_path.clone(),
-            subcommand,
-            depth + 1,
-        )?;
-    }
+    writeln!(buffer, "{}\n", header.trim_end())?;
 
     Ok(())
 }
-*/
 
-fn build_command_markdown(
-    buffer: &mut String,
-    // Parent commands of `command`.
-    parent_command_path: Vec<String>,
+/// Write the default document title, introduction, and table of contents.
+fn write_default_document_header(
+    buffer: &mut dyn Write,
     command: &clap::Command,
-    depth: usize,
-) -> std::fmt::Result {
-    // Don't document commands marked with `clap(hide = true)` (which includes
-    // `print-all-help`).
-    if command.is_hide_set() {
+    options: &MarkdownOptions,
+    links: CommandLinks,
+) -> fmt::Result {
+    //----------------------------------
+    // Write the document title
+    //----------------------------------
+
+    options
+        .active_renderer()
+        .write_title(buffer, options, command)?;
+
+    //----------------------------------
+    // Write the table of contents
+    //----------------------------------
+
+    if options.show_table_of_contents {
+        writeln!(buffer, "**Command Overview:**\n")?;
+
+        build_table_of_contents_markdown(buffer, options, links, command)?;
+
+        writeln!(buffer)?;
+    }
+
+    Ok(())
+}
+
+fn write_footer(
+    buffer: &mut dyn Write,
+    options: &MarkdownOptions,
+    // The generation date of the document.
+    date: &str,
+    // The root command of the document.
+    command: &clap::Command,
+) -> fmt::Result {
+    if !options.show_footer {
+        return Ok(());
+    }
+
+    let renderer = options.active_renderer();
+
+    let Some(ref template) = options.footer_template else {
+        return renderer.write_footer(buffer, options, command);
+    };
+
+    let default_footer = write_to_string(|buffer| {
+        renderer.write_footer(buffer, options, command)
+    })?;
+
+    let footer = render_document_template(
+        template,
+        date,
+        command,
+        &[("default_footer", default_footer.trim_end())],
+    );
+
+    writeln!(buffer, "{}", footer.trim_end())?;
+
+    Ok(())
+}
+
+/// Write the YAML front matter of the document or page documenting `command`,
+/// if enabled.
+fn write_front_matter(
+    buffer: &mut dyn Write,
+    options: &MarkdownOptions,
+    // The generation date of the document.
+    date: &str,
+    // The root command of the document.
+    root_command: &clap::Command,
+    // Parent commands of `command`.
+    parent_command_path: &[String],
+    command: &clap::Command,
+    // The position of the page.
+    weight: usize,
+) -> fmt::Result {
+    let Some(ref template) = options.front_matter else {
+        return Ok(());
+    };
+
+    let mut command_path = parent_command_path.to_vec();
+    command_path.push(get_canonical_name(command));
+
+    let title = match options.title {
+        Some(ref title) if parent_command_path.is_empty() => title.to_owned(),
+        _ => command_path.join(" "),
+    };
+
+    let page_path = command_page_path(&command_path);
+    let slug = page_path.strip_suffix(".md").unwrap_or(&page_path);
+
+    let front_matter = render_template(
+        template,
+        &[
+            ("title", &yaml_string(&title)),
+            ("name", &yaml_string(&get_canonical_name(command))),
+            ("command_path", &yaml_string(&command_path.join(" "))),
+            (
+                "about",
+                &yaml_string(
+                    &command.get_about().map(plain_text).unwrap_or_default(),
+                ),
+            ),
+            (
+                "version",
+                &yaml_string(root_command.get_version().unwrap_or_default()),
+            ),
+            ("date", &yaml_string(date)),
+            ("weight", &weight.to_string()),
+            ("slug", &yaml_string(slug)),
+        ],
+    );
+
+    writeln!(buffer, "---\n{}\n---\n", front_matter.trim_end())?;
+
+    Ok(())
+}
+
+/// Render a template for part of the document documenting `command`, which
+/// supports the `{{name}}`, `{{version}}`, and `{{date}}` variables, in
+/// addition to `variables`.
+fn render_document_template(
+    template: &str,
+    // The generation date of the document.
+    date: &str,
+    // The root command of the document.
+    command: &clap::Command,
+    variables: &[(&str, &str)],
+) -> String {
+    let name = get_canonical_name(command);
+
+    let mut all_variables = vec![
+        ("name", name.as_str()),
+        ("version", command.get_version().unwrap_or_default()),
+        ("date", date),
+    ];
+    all_variables.extend_from_slice(variables);
+
+    return render_template(template, &all_variables);
+}
+
+/// Get the title of the document documenting `command`.
+fn document_title(
+    options: &MarkdownOptions,
+    command: &clap::Command,
+) -> String {
+    match options.title {
+        Some(ref title) if options.flavor == MarkdownFlavor::Mdx => {
+            escape_mdx(title)
+        },
+        Some(ref title) => title.to_owned(),
+        None => {
+            format!("Command-Line Help for `{}`", get_canonical_name(command))
+        },
+    }
+}
+
+/// Build a separate Markdown page for `command` and each of its documented
+/// subcommands, inserting them into `pages`.
+fn build_markdown_pages(
+    pages: &mut BTreeMap<String, String>,
+    options: &MarkdownOptions,
+    // The generation date of the document.
+    date: &str,
+    // The root command of the document.
+    root_command: &clap::Command,
+) -> fmt::Result {
+    for node in documented_commands(root_command) {
+        let command = node.command;
+        let parent_command_path = node.parent_command_path();
+
+        let page_path = command_page_path(&node.command_path);
+        let links = CommandLinks::Page {
+            current_page: &page_path,
+        };
+
+        let mut buffer = String::with_capacity(100);
+
+        // Pages are inserted in the order of the table of contents.
+        let weight = pages.len() + 1;
+
+        write_front_matter(
+            &mut buffer,
+            options,
+            date,
+            root_command,
+            parent_command_path,
+            command,
+            weight,
+        )?;
+
+        if parent_command_path.is_empty() {
+            // The root command page doubles as the index page of the document.
+            write_document_header(&mut buffer, command, options, date, links)?;
+
+            write_command_section(
+                &mut buffer,
+                options,
+                links,
+                parent_command_path,
+                &node.inherited_globals,
+                command,
+                options.command_heading_level(0),
+            )?;
+
+            if options.env_vars_section == EnvVarsSection::Document {
+                write_document_env_vars_markdown(
+                    &mut buffer,
+                    options,
+                    command,
+                )?;
+            }
+        } else {
+            // The command is the subject of this page, so use a top-level
+            // heading.
+            write_command_section(
+                &mut buffer,
+                options,
+                links,
+                parent_command_path,
+                &node.inherited_globals,
+                command,
+                1,
+            )?;
+        }
+
+        write_footer(&mut buffer, options, date, root_command)?;
+
+        pages.insert(page_path, buffer);
+    }
+
+    Ok(())
+}
+
+fn build_table_of_contents_markdown(
+    buffer: &mut dyn Write,
+    options: &MarkdownOptions,
+    links: CommandLinks,
+    command: &clap::Command,
+) -> std::fmt::Result {
+    for node in documented_commands(command) {
+        if options
+            .table_of_contents_max_depth
+            .is_some_and(|max_depth| node.depth() > max_depth)
+        {
+            continue;
+        }
+
+        options.active_renderer().write_table_of_contents_entry(
+            buffer,
+            options,
+            &TocEntry {
+                command: node.command,
+                command_path: &node.command_path,
+                depth: node.depth(),
+                href: links.href(&node.command_path),
+                is_same_page: matches!(links, CommandLinks::Anchor(_)),
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+fn build_summary_markdown(
+    buffer: &mut dyn Write,
+    pages_dir: &str,
+    command: &clap::Command,
+) -> fmt::Result {
+    for node in documented_commands(command) {
+        let page_path = command_page_path(&node.command_path);
+
+        let page_path = match pages_dir.trim_end_matches('/') {
+            "" => page_path,
+            pages_dir => format!("{pages_dir}/{page_path}"),
+        };
+
+        writeln!(
+            buffer,
+            "{}- [{}]({page_path})",
+            "  ".repeat(node.depth()),
+            node.command_path.last().unwrap(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Write a section listing the environment variables used by every documented
+/// command in `command`, and the arguments they are bound to.
+fn write_document_env_vars_markdown(
+    buffer: &mut dyn Write,
+    options: &MarkdownOptions,
+    command: &clap::Command,
+) -> fmt::Result {
+    // Map from environment variable name to the arguments bound to it, e.g.
+    // `tool sub --foo`.
+    let mut env_vars: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    build_env_vars(&mut env_vars, command);
+
+    if env_vars.is_empty() {
         return Ok(());
     }
 
-    let title_name = get_canonical_name(command);
+    writeln!(
+        buffer,
+        "{} Environment Variables\n",
+        "#".repeat(options.command_heading_level(0)),
+    )?;
+
+    for (env, args) in env_vars {
+        let args: Vec<String> =
+            args.iter().map(|arg| format!("`{arg}`")).collect();
+
+        writeln!(buffer, "* `{env}` — {}", args.join(", "))?;
+    }
 
+    writeln!(buffer)?;
+
+    // Include extra space before the footer, like between commands. This is
+    // purely for the benefit of anyone reading the source .md file.
+    write!(buffer, "\n\n")?;
+
+    Ok(())
+}
+
+fn build_env_vars(
+    env_vars: &mut BTreeMap<String, Vec<String>>,
+    command: &clap::Command,
+) {
+    for node in documented_commands(command) {
+        for arg in node
+            .command
+            .get_arguments()
+            .filter(|arg| should_show_arg(arg))
+            .filter(|arg| !is_inherited_global(&node.inherited_globals, arg))
+        {
+            if let Some(env) = arg_env(arg) {
+                env_vars.entry(env).or_default().push(format!(
+                    "{} {}",
+                    node.command_path.join(" "),
+                    arg_display_name(arg)
+                ));
+            }
+        }
+    }
+}
+
+fn build_command_markdown(
+    buffer: &mut dyn Write,
+    options: &MarkdownOptions,
+    links: CommandLinks,
+    command: &clap::Command,
+) -> std::fmt::Result {
+    for node in documented_commands(command) {
+        write_command_section(
+            buffer,
+            options,
+            links,
+            node.parent_command_path(),
+            &node.inherited_globals,
+            node.command,
+            options.command_heading_level(node.depth()),
+        )?;
+
+        // Include extra space between commands. This is purely for the
+        // benefit of anyone reading the source .md file.
+        write!(buffer, "\n\n")?;
+    }
+
+    Ok(())
+}
+
+/// Write the heading and help content for `command`, not including its
+/// subcommands.
+fn write_command_section(
+    buffer: &mut dyn Write,
+    options: &MarkdownOptions,
+    links: CommandLinks,
+    // Parent commands of `command`.
+    parent_command_path: &[String],
+    // Global arguments declared by the parent commands of `command`.
+    inherited_globals: &[GlobalArg],
+    command: &clap::Command,
+    heading_level: usize,
+) -> fmt::Result {
     // Append the name of `command` to `command_path`.
     let command_path = {
-        let mut command_path = parent_command_path.clone();
-        command_path.push(title_name);
+        let mut command_path = parent_command_path.to_vec();
+        command_path.push(get_canonical_name(command));
         command_path
     };
 
+    let renderer = options.active_renderer();
+
+    let cx = CommandContext {
+        options,
+        links,
+        parent_command_path,
+        command_path: &command_path,
+        command,
+        heading_level,
+    };
+
+    if let Some(ref template) = options.command_template {
+        let heading = write_to_string(|buffer| {
+            renderer.write_command_heading(buffer, &cx)
+        })?;
+
+        let section = render_template(
+            template,
+            &[
+                ("heading", heading.trim_end()),
+                ("command_path", &command_path.join(" ")),
+                ("name", &get_canonical_name(command)),
+                ("version", command.get_version().unwrap_or_default()),
+                ("about", &command_about(options, command)),
+                (
+                    "before_help",
+                    &styled_markdown_or_empty(
+                        options,
+                        command,
+                        command
+                            .get_before_long_help()
+                            .or(command.get_before_help()),
+                    ),
+                ),
+                (
+                    "after_help",
+                    &styled_markdown_or_empty(
+                        options,
+                        command,
+                        command
+                            .get_after_long_help()
+                            .or(command.get_after_help()),
+                    ),
+                ),
+                ("usage", &command_usage(parent_command_path, command)),
+                (
+                    "aliases",
+                    &if options.show_aliases {
+                        code_list(&command_aliases(command))
+                    } else {
+                        String::new()
+                    },
+                ),
+                (
+                    "subcommands",
+                    &write_to_string(|buffer| {
+                        write_subcommands_section(buffer, &cx)
+                    })?,
+                ),
+                (
+                    "arguments",
+                    &write_to_string(|buffer| {
+                        write_arg_sections(buffer, &cx, inherited_globals)
+                    })?,
+                ),
+                (
+                    "arg_groups",
+                    &write_to_string(|buffer| {
+                        write_arg_groups_section(buffer, &cx)
+                    })?,
+                ),
+                (
+                    "env_vars",
+                    &write_to_string(|buffer| {
+                        write_command_env_vars_section(
+                            buffer,
+                            &cx,
+                            inherited_globals,
+                        )
+                    })?,
+                ),
+            ],
+        );
+
+        writeln!(buffer, "{}\n", section.trim_end())?;
+
+        return Ok(());
+    }
+
     //----------------------------------
     // Write the markdown heading
     //----------------------------------
 
-    // TODO: `depth` is now unused. Remove if no other use for it appears.
-    /*
-    if depth >= 6 {
-        panic!(
-            "command path nesting depth is deeper than maximum markdown header depth: `{}`",
-            command_path.join(" ")
+    renderer.write_command_header(buffer, &cx)?;
+
+    renderer.write_usage(buffer, &cx)?;
+
+    let aliases = command_aliases(command);
+
+    if options.show_aliases && !aliases.is_empty() {
+        if aliases.len() > 1 {
+            // Plural
+            writeln!(buffer, "**Command Aliases:** {}\n", code_list(&aliases))?;
+        } else {
+            // Singular
+            writeln!(buffer, "**Command Alias:** {}\n", code_list(&aliases))?;
+        }
+    }
+
+    if let Some(help) = command.get_after_long_help() {
+        writeln!(buffer, "{}\n", styled_markdown(options, command, help))?;
+    } else if let Some(help) = command.get_after_help() {
+        writeln!(buffer, "{}\n", styled_markdown(options, command, help))?;
+    }
+
+    write_subcommands_section(buffer, &cx)?;
+
+    write_arg_sections(buffer, &cx, inherited_globals)?;
+
+    write_arg_groups_section(buffer, &cx)?;
+
+    write_command_env_vars_section(buffer, &cx, inherited_globals)?;
+
+    Ok(())
+}
+
+/// Write the list of the subcommands of the command documented by `cx`.
+fn write_subcommands_section(
+    buffer: &mut dyn Write,
+    cx: &CommandContext,
+) -> fmt::Result {
+    let options = cx.options;
+    let command = cx.command;
+
+    if command.get_subcommands().next().is_none() {
+        return Ok(());
+    }
+
+    writeln!(buffer, "{}\n", options.section_heading("Subcommands"))?;
+
+    for subcommand in command.get_subcommands() {
+        if subcommand.is_hide_set() {
+            continue;
+        }
+
+        let title_name = get_canonical_name(subcommand);
+
+        let about = match subcommand.get_about() {
+            Some(about) => styled_markdown(options, subcommand, about),
+            None => String::new(),
+        };
+
+        match cx.links {
+            CommandLinks::Anchor(_) => write!(buffer, "* `{title_name}`")?,
+            CommandLinks::Page { .. } => {
+                let mut subcommand_path = cx.command_path.to_vec();
+                subcommand_path.push(title_name.clone());
+
+                write!(
+                    buffer,
+                    "* [`{title_name}`]({})",
+                    cx.href(&subcommand_path)
+                )?
+            },
+        }
+
+        let aliases = command_aliases(subcommand);
+
+        if options.show_aliases && !aliases.is_empty() {
+            write!(
+                buffer,
+                " ({}: {})",
+                if aliases.len() > 1 {
+                    "aliases"
+                } else {
+                    "alias"
+                },
+                code_list(&aliases)
+            )?;
+        }
+
+        writeln!(buffer, " — {about}")?;
+    }
+
+    writeln!(buffer)?;
+
+    Ok(())
+}
+
+/// Write the sections documenting the arguments of the command documented by
+/// `cx`, followed by links to the global options it inherits.
+fn write_arg_sections(
+    buffer: &mut dyn Write,
+    cx: &CommandContext,
+    // Global arguments declared by the parent commands of the command.
+    inherited_globals: &[GlobalArg],
+) -> fmt::Result {
+    let renderer = cx.options.active_renderer();
+
+    for section in command_arg_sections(cx.command, inherited_globals) {
+        // Set the id of the section explicitly, since the generated ids of
+        // section headings are the same in every command section.
+        if section.is_global_options {
+            writeln!(
+                buffer,
+                "<a id=\"{}\"></a>\n",
+                cx.links.global_options_anchor(cx.command_path)
+            )?;
+        }
+
+        renderer.write_arg_section(
+            buffer,
+            cx,
+            section.heading,
+            &section.args,
+        )?;
+    }
+
+    // Link to the global options inherited from parent commands, instead of
+    // repeating them in every subcommand.
+    for parent_path in global_parents(inherited_globals) {
+        let href = if has_global_options_section(inherited_globals, parent_path)
+        {
+            cx.links.global_options_href(parent_path)
+        } else {
+            cx.href(parent_path)
+        };
+
+        writeln!(
+            buffer,
+            "This command also accepts the [global options]({href}) of `{}`.\n",
+            parent_path.join(" ")
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Write the rules imposed by the argument groups of the command documented by
+/// `cx`.
+fn write_arg_groups_section(
+    buffer: &mut dyn Write,
+    cx: &CommandContext,
+) -> fmt::Result {
+    let groups = arg_group_rules(cx.command);
+
+    if groups.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(
+        buffer,
+        "{}\n",
+        cx.options.section_heading("Argument Groups")
+    )?;
+
+    for group in groups {
+        let members: Vec<String> =
+            group.members.into_iter().map(arg_display_name).collect();
+
+        writeln!(
+            buffer,
+            "* `{}` — {} {} {} be given",
+            group.id,
+            group.rule,
+            code_list(&members),
+            group.verb,
+        )?;
+    }
+
+    writeln!(buffer)?;
+
+    Ok(())
+}
+
+/// Write the environment variables used by the arguments of the command
+/// documented by `cx`, if [`EnvVarsSection::PerCommand`] is used.
+fn write_command_env_vars_section(
+    buffer: &mut dyn Write,
+    cx: &CommandContext,
+    // Global arguments declared by the parent commands of the command.
+    inherited_globals: &[GlobalArg],
+) -> fmt::Result {
+    if cx.options.env_vars_section != EnvVarsSection::PerCommand {
+        return Ok(());
+    }
+
+    let env_vars: Vec<(String, &clap::Arg)> =
+        documented_args(cx.command, inherited_globals)
+            .into_iter()
+            .filter_map(|arg| Some((arg_env(arg)?, arg)))
+            .collect();
+
+    if env_vars.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(
+        buffer,
+        "{}\n",
+        cx.options.section_heading("Environment Variables")
+    )?;
+
+    for (env, arg) in env_vars {
+        writeln!(buffer, "* `{env}` — `{}`", arg_display_name(arg))?;
+    }
+
+    writeln!(buffer)?;
+
+    Ok(())
+}
+
+/// Get the description of `command`, preferring its long description.
+fn command_about(options: &MarkdownOptions, command: &clap::Command) -> String {
+    return styled_markdown_or_empty(
+        options,
+        command,
+        command.get_long_about().or(command.get_about()),
+    );
+}
+
+//======================================
+// Command structure
+//======================================
+
+/// A documented command, as listed by [`documented_commands()`].
+struct CommandNode<'a> {
+    command: &'a clap::Command,
+    /// Names of the command and its parent commands.
+    command_path: Vec<String>,
+    /// Global arguments declared by the parent commands of the command.
+    inherited_globals: Vec<GlobalArg>,
+}
+
+impl CommandNode<'_> {
+    /// Names of the parent commands of the command.
+    fn parent_command_path(&self) -> &[String] {
+        return &self.command_path[..self.depth()];
+    }
+
+    /// The number of parent commands of the command, i.e. `0` for the root
+    /// command.
+    fn depth(&self) -> usize {
+        return self.command_path.len() - 1;
+    }
+}
+
+/// Get `command` and each of its subcommands that are documented, in the order
+/// their sections are written: depth first, like the table of contents.
+///
+/// Commands marked with `clap(hide = true)` (which includes `print-all-help`)
+/// are not documented, and neither are their subcommands.
+fn documented_commands(command: &clap::Command) -> Vec<CommandNode<'_>> {
+    let mut nodes = Vec::new();
+
+    build_documented_commands(&mut nodes, Vec::new(), &[], command);
+
+    return nodes;
+}
+
+fn build_documented_commands<'a>(
+    nodes: &mut Vec<CommandNode<'a>>,
+    // Parent commands of `command`.
+    parent_command_path: Vec<String>,
+    // Global arguments declared by the parent commands of `command`.
+    inherited_globals: &[GlobalArg],
+    command: &'a clap::Command,
+) {
+    if command.is_hide_set() {
+        return;
+    }
+
+    // Append the name of `command` to `command_path`.
+    let command_path = {
+        let mut command_path = parent_command_path;
+        command_path.push(get_canonical_name(command));
+        command_path
+    };
+
+    let globals = subcommand_globals(inherited_globals, &command_path, command);
+
+    nodes.push(CommandNode {
+        command,
+        command_path: command_path.clone(),
+        inherited_globals: inherited_globals.to_vec(),
+    });
+
+    for subcommand in command.get_subcommands() {
+        build_documented_commands(
+            nodes,
+            command_path.clone(),
+            &globals,
+            subcommand,
+        );
+    }
+}
+
+/// Get the usage of `command`, including the names of its parent commands,
+/// e.g. `tool remote add [OPTIONS] <NAME>`.
+fn command_usage(
+    // Parent commands of `command`.
+    parent_command_path: &[String],
+    command: &clap::Command,
+) -> String {
+    let usage = command
+        .clone()
+        .render_usage()
+        .to_string()
+        .replace("Usage: ", "");
+
+    if parent_command_path.is_empty() {
+        return usage;
+    }
+
+    return format!("{} {usage}", parent_command_path.join(" "));
+}
+
+/// A section of the documented arguments of a command, e.g. "Options".
+struct ArgSection<'a> {
+    heading: &'a str,
+    args: Vec<&'a clap::Arg>,
+    /// Whether this is the "Global Options" section, which the subcommands of
+    /// the command link to.
+    is_global_options: bool,
+}
+
+/// Get the arguments of `command` that should be documented in its section.
+///
+/// Global arguments are only documented in the section of the command that
+/// declares them, even though clap propagates them to every subcommand.
+fn documented_args<'a>(
+    command: &'a clap::Command,
+    inherited_globals: &[GlobalArg],
+) -> Vec<&'a clap::Arg> {
+    return command
+        .get_arguments()
+        .filter(|arg| should_show_arg(arg))
+        .filter(|arg| !is_inherited_global(inherited_globals, arg))
+        .collect();
+}
+
+/// Get the non-empty sections that the documented arguments of `command` are
+/// shown in, in order.
+///
+/// Like clap's own help output, arguments that have a custom help heading are
+/// documented in a separate section with that heading, instead of in the
+/// default "Arguments" or "Options" section.
+fn command_arg_sections<'a>(
+    command: &'a clap::Command,
+    inherited_globals: &[GlobalArg],
+) -> Vec<ArgSection<'a>> {
+    let args = documented_args(command, inherited_globals);
+
+    let section = |heading: &'a str,
+                   is_global_options: bool,
+                   filter: &dyn Fn(&clap::Arg) -> bool| {
+        let mut section_args: Vec<&clap::Arg> =
+            args.iter().copied().filter(|arg| filter(arg)).collect();
+
+        // Positional arguments are shown in the order they are given.
+        if heading != "Arguments" {
+            section_args.sort_by_key(|arg| option_sort_key(arg));
+        }
+
+        ArgSection {
+            heading,
+            args: section_args,
+            is_global_options,
+        }
+    };
+
+    let mut sections = vec![
+        section("Arguments", false, &|arg| {
+            arg.is_positional() && arg.get_help_heading().is_none()
+        }),
+        section("Options", false, &|arg| {
+            !arg.is_positional()
+                && arg.get_help_heading().is_none()
+                && !arg.is_global_set()
+        }),
+        section("Global Options", true, &is_global_options_arg),
+    ];
+
+    // Custom headings are shown in the order they are first used.
+    let mut help_headings: Vec<&str> = Vec::new();
+
+    for heading in args.iter().filter_map(|arg| arg.get_help_heading()) {
+        if !help_headings.contains(&heading) {
+            help_headings.push(heading);
+        }
+    }
+
+    for heading in help_headings {
+        sections.push(section(heading, false, &|arg| {
+            arg.get_help_heading() == Some(heading)
+        }));
+    }
+
+    sections.retain(|section| !section.args.is_empty());
+
+    return sections;
+}
+
+/// Whether `arg` is documented in the "Global Options" section of the command
+/// that declares it, instead of in a section with a custom help heading.
+fn is_global_options_arg(arg: &clap::Arg) -> bool {
+    return !arg.is_positional()
+        && arg.get_help_heading().is_none()
+        && arg.is_global_set();
+}
+
+/// Whether the command at `command_path` has a "Global Options" section
+/// documenting some of the global arguments in `inherited_globals`.
+///
+/// Global arguments that all have custom help headings are not documented in
+/// a "Global Options" section, so links to them point to the section of the
+/// command that declares them instead.
+fn has_global_options_section(
+    inherited_globals: &[GlobalArg],
+    command_path: &[String],
+) -> bool {
+    return inherited_globals.iter().any(|global| {
+        global.command_path == command_path && global.in_global_options
+    });
+}
+
+/// Get the paths of the commands that declare the global arguments inherited
+/// by a command, in order.
+fn global_parents(inherited_globals: &[GlobalArg]) -> Vec<&[String]> {
+    let mut parents: Vec<&[String]> = Vec::new();
+
+    for global in inherited_globals {
+        if !parents.contains(&global.command_path.as_slice()) {
+            parents.push(&global.command_path);
+        }
+    }
+
+    return parents;
+}
+
+/// A rule, imposed by an argument group, on how its arguments can be used.
+struct ArgGroupRule<'a> {
+    id: &'a str,
+    /// Whether one of the arguments of the group must be used.
+    required: bool,
+    /// Whether more than one of the arguments of the group can be used.
+    multiple: bool,
+    /// E.g. "Exactly one of".
+    rule: &'static str,
+    /// E.g. "must".
+    verb: &'static str,
+    members: Vec<&'a clap::Arg>,
+}
+
+/// Get the rules imposed by the argument groups of `command`.
+///
+/// Only groups that constrain how their arguments can be used are included.
+/// This skips the groups generated by `#[derive(Args)]`, which allow any
+/// combination of their arguments.
+fn arg_group_rules(command: &clap::Command) -> Vec<ArgGroupRule<'_>> {
+    let mut rules = Vec::new();
+
+    for group in command.get_groups() {
+        let multiple = group.clone().is_multiple();
+
+        let (rule, verb) = match (group.is_required_set(), multiple) {
+            (true, false) => ("Exactly one of", "must"),
+            (true, true) => ("At least one of", "must"),
+            (false, false) => ("At most one of", "may"),
+            (false, true) => continue,
+        };
+
+        let members: Vec<&clap::Arg> = group
+            .get_args()
+            .filter_map(|id| {
+                command.get_arguments().find(|arg| arg.get_id() == id)
+            })
+            .filter(|arg| should_show_arg(arg))
+            .collect();
+
+        if members.is_empty() {
+            continue;
+        }
+
+        rules.push(ArgGroupRule {
+            id: group.get_id().as_str(),
+            required: group.is_required_set(),
+            multiple,
+            rule,
+            verb,
+            members,
+        });
+    }
+
+    return rules;
+}
+
+/// Get notes about how `arg` can be used, e.g. "required".
+fn arg_notes(arg: &clap::Arg) -> Vec<&'static str> {
+    let mut notes: Vec<&str> = Vec::new();
+
+    if arg.is_required_set() {
+        notes.push("required");
+    }
+
+    // Repeated positional arguments are indicated by `...` in the value
+    // notation instead.
+    if !arg.is_positional()
+        && matches!(
+            arg.get_action(),
+            clap::ArgAction::Append | clap::ArgAction::Count
         )
+    {
+        notes.push("may be repeated");
+    }
+
+    return notes;
+}
+
+/// Get the possible values of `arg` that should be documented.
+fn arg_possible_values(arg: &clap::Arg) -> Vec<PossibleValue> {
+    // Don't document the possible values of flags that can only be either
+    // present or absent and do not take a value.
+    if matches!(arg.get_action(), clap::ArgAction::SetTrue) {
+        return Vec::new();
+    }
+
+    return arg
+        .get_possible_values()
+        .into_iter()
+        .filter(|pv| !pv.is_hide_set())
+        .collect();
+}
+
+/// The details documented about an argument.
+///
+/// Every output format documents the same details of an argument, and only
+/// differs in how it formats them.
+struct ArgDetails<'a> {
+    /// The short and long flags of the argument, e.g. `-c` and `--config`.
+    /// Empty for positional arguments.
+    flags: Vec<String>,
+    /// See [`arg_value_notation()`].
+    value_notation: String,
+    /// See [`arg_notes()`].
+    notes: Vec<&'static str>,
+    /// The long help text of the argument, or else its short help text.
+    help: Option<&'a StyledStr>,
+    /// See [`arg_aliases()`].
+    aliases: Vec<String>,
+    /// Whether the argument cannot be used with any other argument.
+    exclusive: bool,
+    /// The display names of the arguments that cannot be used together with
+    /// the argument. See [`arg_conflicts()`].
+    conflicts: Vec<String>,
+    /// See [`arg_env()`].
+    env: Option<String>,
+    /// The default values of the argument.
+    default_values: Vec<String>,
+    /// See [`arg_possible_values()`].
+    possible_values: Vec<PossibleValue>,
+}
+
+impl ArgDetails<'_> {
+    /// Get the names of the argument, with the value notation following the
+    /// last flag, e.g. `-c` and `--config <FILE>`, or `<FILE>` for a
+    /// positional argument.
+    fn names(&self) -> Vec<String> {
+        let mut names = self.flags.clone();
+
+        match names.last_mut() {
+            Some(last) => last.push_str(&self.value_notation),
+            None => names.push(self.value_notation.clone()),
+        }
+
+        return names;
+    }
+}
+
+/// Get the details documented about `arg`, an argument of `command`.
+fn arg_details<'a>(
+    command: &clap::Command,
+    arg: &'a clap::Arg,
+) -> ArgDetails<'a> {
+    let flags: Vec<String> = arg
+        .get_short()
+        .map(|short| format!("-{short}"))
+        .into_iter()
+        .chain(arg.get_long().map(|long| format!("--{long}")))
+        .collect();
+
+    debug_assert!(
+        !flags.is_empty() || arg.is_positional(),
+        "unexpected non-positional Arg with neither short nor long name: {arg:?}"
+    );
+
+    return ArgDetails {
+        flags,
+        value_notation: arg_value_notation(arg),
+        notes: arg_notes(arg),
+        help: arg.get_long_help().or(arg.get_help()),
+        aliases: arg_aliases(arg),
+        exclusive: arg.is_exclusive_set(),
+        conflicts: arg_conflicts(command, arg)
+            .into_iter()
+            .map(arg_display_name)
+            .collect(),
+        env: arg_env(arg),
+        default_values: arg
+            .get_default_values()
+            .iter()
+            .map(|value| value.to_string_lossy().into_owned())
+            .collect(),
+        possible_values: arg_possible_values(arg),
+    };
+}
+
+//======================================
+// Styled text
+//======================================
+
+/// The emphasis of a run of styled help text.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct TextEmphasis {
+    /// Literal command-line syntax, shown as inline code.
+    literal: bool,
+    /// Bold text.
+    strong: bool,
+    /// Underlined text.
+    emphasis: bool,
+}
+
+/// Split the styled help text of `command` into runs of text with the same
+/// emphasis.
+///
+/// Text styled using the literal style of `command` (see
+/// [`clap::builder::Styles::literal()`]) is literal, and other bold and
+/// underlined text is strong and emphasized text. Other styling, like colors,
+/// is discarded.
+///
+/// If the literal style is plain bold, as it is by default, bold text can't
+/// be told apart from literal text, and is treated as strong text. Likewise,
+/// no text is treated as literal if the literal style is unstyled (e.g. with
+/// [`Styles::plain()`](clap::builder::Styles::plain)), or is the same as the
+/// header style.
+///
+/// A span may contain line breaks, and even blank lines. The output formats
+/// style each line of a span separately, so that the text can still be split
+/// into paragraphs at blank lines afterwards.
+fn emphasized_spans(
+    command: &clap::Command,
+    text: &StyledStr,
+) -> Vec<(TextEmphasis, String)> {
+    let styles = command.get_styles();
+    let literal = *styles.get_literal();
+    let literal_is_distinct = literal != Style::new()
+        && literal != Style::new().bold()
+        && literal != *styles.get_header();
+
+    let underlines = [
+        Effects::UNDERLINE,
+        Effects::DOUBLE_UNDERLINE,
+        Effects::CURLY_UNDERLINE,
+        Effects::DOTTED_UNDERLINE,
+        Effects::DASHED_UNDERLINE,
+    ];
+
+    let mut spans: Vec<(TextEmphasis, String)> = Vec::new();
+
+    for (style, text) in styled_spans(&styled_str_ansi(text)) {
+        let effects = style.get_effects();
+
+        let emphasis = if literal_is_distinct && style == literal {
+            TextEmphasis {
+                literal: true,
+                strong: false,
+                emphasis: false,
+            }
+        } else {
+            TextEmphasis {
+                literal: false,
+                strong: effects.contains(Effects::BOLD),
+                emphasis: underlines
+                    .into_iter()
+                    .any(|underline| effects.contains(underline)),
+            }
+        };
+
+        match spans.last_mut() {
+            Some((last, last_text)) if *last == emphasis => {
+                last_text.push_str(&text)
+            },
+            _ => spans.push((emphasis, text)),
+        }
     }
-    */
 
-    writeln!(
-        buffer,
-        "{} `{}`\n",
-        // "#".repeat(depth + 1),
-        "##",
-        command_path.join(" "),
-    )?;
+    return spans;
+}
+
+/// Convert styled help text of `command` to Markdown.
+///
+/// Literal text is shown as inline code, and strong and emphasized text use
+/// `**` and `_` (see [`emphasized_spans()`]).
+///
+/// If the help text format is [`HelpTextFormat::PlainText`], Markdown
+/// metacharacters in the text (other than inline code) are escaped.
+fn styled_markdown(
+    options: &MarkdownOptions,
+    command: &clap::Command,
+    text: &StyledStr,
+) -> String {
+    let mut markdown = String::new();
+
+    for (emphasis, text) in emphasized_spans(command, text) {
+        let is_literal = emphasis.literal;
+
+        let (open, close) = if is_literal {
+            // Use a longer code span delimiter if the text contains backticks.
+            let mut longest_run = 0;
+            let mut run = 0;
+            for c in text.chars() {
+                run = if c == '`' { run + 1 } else { 0 };
+                longest_run = longest_run.max(run);
+            }
+
+            let fence = "`".repeat(longest_run + 1);
+
+            if text.starts_with('`') || text.ends_with('`') {
+                (format!("{fence} "), format!(" {fence}"))
+            } else {
+                (fence.clone(), fence)
+            }
+        } else {
+            match (emphasis.strong, emphasis.emphasis) {
+                (true, true) => ("**_".to_owned(), "_**".to_owned()),
+                (true, false) => ("**".to_owned(), "**".to_owned()),
+                (false, true) => ("_".to_owned(), "_".to_owned()),
+                (false, false) => (String::new(), String::new()),
+            }
+        };
+
+        // Markdown inline styling can't span paragraphs, and the delimiters
+        // must be adjacent to the styled text, so style each line
+        // separately, and keep surrounding whitespace outside the delimiters.
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 {
+                markdown.push('\n');
+            }
+
+            let content = line.trim();
+
+            if content.is_empty() {
+                markdown.push_str(line);
+                continue;
+            }
+
+            let start = line.len() - line.trim_start().len();
+            let end = start + content.len();
+
+            let content = match (options.help_text_format, options.flavor) {
+                _ if is_literal => content.to_owned(),
+                (HelpTextFormat::PlainText, MarkdownFlavor::Standard) => {
+                    escape_markdown(content)
+                },
+                (HelpTextFormat::PlainText, MarkdownFlavor::Mdx) => {
+                    escape_markdown(content)
+                        .replace('{', "\\{")
+                        .replace('}', "\\}")
+                },
+                (HelpTextFormat::Markdown, MarkdownFlavor::Standard) => {
+                    content.to_owned()
+                },
+                (HelpTextFormat::Markdown, MarkdownFlavor::Mdx) => {
+                    escape_mdx(content)
+                },
+            };
+
+            markdown.push_str(&line[..start]);
+            markdown.push_str(&open);
+            markdown.push_str(&content);
+            markdown.push_str(&close);
+            markdown.push_str(&line[end..]);
+        }
+    }
+
+    if options.help_text_format == HelpTextFormat::PlainText {
+        markdown = escape_markdown_line_starts(&markdown);
+    }
+
+    return markdown;
+}
+
+/// Escape characters in `text` that have a special meaning anywhere in
+/// Markdown or HTML inline content.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~' | '&'
+        ) {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    return escaped;
+}
+
+/// Escape the characters in `text` that MDX interprets as the start of JSX
+/// (`<`) or of a JavaScript expression (`{` and `}`).
+///
+/// Code spans in `text` are left unchanged, since MDX doesn't interpret their
+/// content.
+fn escape_mdx(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '`' {
+            let fence_len = rest.len() - rest.trim_start_matches('`').len();
+
+            // Copy the code span verbatim, up to and including the closing
+            // backtick run of the same length.
+            let span_len = match closing_fence(&rest[fence_len..], fence_len) {
+                Some(end) => fence_len + end + fence_len,
+                // Not a code span, just literal backticks.
+                None => fence_len,
+            };
+
+            escaped.push_str(&rest[..span_len]);
+            rest = &rest[span_len..];
+            continue;
+        }
+
+        if matches!(c, '<' | '{' | '}') {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    return escaped;
+}
+
+/// Find the offset of the first run of exactly `fence_len` backticks in
+/// `text`.
+fn closing_fence(text: &str, fence_len: usize) -> Option<usize> {
+    let mut offset = 0;
+
+    while let Some(start) = text[offset..].find('`') {
+        let start = offset + start;
+        let run =
+            text[start..].len() - text[start..].trim_start_matches('`').len();
+
+        if run == fence_len {
+            return Some(start);
+        }
+
+        offset = start + run;
+    }
+
+    return None;
+}
+
+/// Escape characters at the start of each line of `text` that would otherwise
+/// start a Markdown heading, list, or thematic break.
+fn escape_markdown_line_starts(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for (index, line) in text.split('\n').enumerate() {
+        if index > 0 {
+            escaped.push('\n');
+        }
+
+        let content = line.trim_start();
+        let start = line.len() - content.len();
+
+        escaped.push_str(&line[..start]);
+
+        // E.g. `# Heading`, `- item`, `+ item`, or `===` (setext heading).
+        if content.starts_with(['#', '-', '+', '=']) {
+            escaped.push('\\');
+            escaped.push_str(content);
+            continue;
+        }
+
+        // E.g. `1. item` or `1) item`.
+        let digits = content.len()
+            - content
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .len();
+
+        if digits > 0 && content[digits..].starts_with(['.', ')']) {
+            escaped.push_str(&content[..digits]);
+            escaped.push('\\');
+            escaped.push_str(&content[digits..]);
+            continue;
+        }
+
+        escaped.push_str(content);
+    }
+
+    return escaped;
+}
+
+/// Get the text of `text`, including the ANSI escape sequences that style it.
+///
+/// clap only provides access to the styling of a [`StyledStr`] if its `color`
+/// feature is enabled, which the `color` feature of this crate enables.
+#[cfg(feature = "color")]
+fn styled_str_ansi(text: &StyledStr) -> String {
+    return text.ansi().to_string();
+}
+
+/// Get the text of `text`, including the ANSI escape sequences that style it.
+///
+/// Without the `color` feature of clap, the ANSI escape sequences are
+/// included when `text` is displayed. If the `color` feature of clap is
+/// enabled by another crate, they are removed, and the text is unstyled.
+#[cfg(not(feature = "color"))]
+fn styled_str_ansi(text: &StyledStr) -> String {
+    return text.to_string();
+}
+
+/// Get the text of `text` without its styling, e.g. for plain text output.
+///
+/// Unlike displaying `text`, this never includes ANSI escape sequences,
+/// whether or not the `color` feature of clap is enabled.
+fn plain_text(text: &StyledStr) -> String {
+    return styled_spans(&styled_str_ansi(text))
+        .into_iter()
+        .map(|(_, text)| text)
+        .collect();
+}
+
+/// Split text containing ANSI escape sequences into runs of text with the
+/// same style.
+///
+/// Escape sequences other than Select Graphic Rendition (SGR) sequences are
+/// discarded, as are malformed and truncated escape sequences.
+fn styled_spans(ansi: &str) -> Vec<(Style, String)> {
+    let mut spans: Vec<(Style, String)> = Vec::new();
+    let mut style = Style::new();
+    let mut rest = ansi;
+
+    while !rest.is_empty() {
+        if let Some(sequence) = rest.strip_prefix("\x1b[") {
+            // Control Sequence Introducer: parameter bytes, followed by
+            // intermediate bytes, followed by a final byte in the range `@` to
+            // `~`.
+            let params_len = sequence
+                .find(|c| !matches!(c, '0'..='?'))
+                .unwrap_or(sequence.len());
+            let end = sequence[params_len..]
+                .find(|c| !matches!(c, ' '..='/'))
+                .map_or(sequence.len(), |i| i + params_len);
+
+            rest = match sequence[end..].chars().next() {
+                Some('m') if end == params_len => {
+                    style = apply_sgr(style, &sequence[..params_len]);
+                    &sequence[end + 1..]
+                },
+                Some(c @ '@'..='~') => &sequence[end + c.len_utf8()..],
+                // Malformed sequence: discard it, but keep the text after it.
+                _ => &sequence[end..],
+            };
+            continue;
+        }
+
+        if let Some(sequence) = rest.strip_prefix("\x1b]") {
+            // Operating System Command (e.g. a hyperlink), terminated by BEL
+            // or by ST (`ESC \`). An unterminated command extends to the end
+            // of the text.
+            rest = match sequence.find(['\x07', '\x1b']) {
+                Some(end) if sequence[end..].starts_with('\x07') => {
+                    &sequence[end + 1..]
+                },
+                Some(end) => sequence[end..]
+                    .strip_prefix("\x1b\\")
+                    .unwrap_or(&sequence[end..]),
+                None => "",
+            };
+            continue;
+        }
+
+        if let Some(sequence) = rest.strip_prefix('\x1b') {
+            // Other escape sequences consist of a single character following
+            // the escape character.
+            let next_len = sequence.chars().next().map_or(0, char::len_utf8);
+            rest = &sequence[next_len..];
+            continue;
+        }
+
+        // Text up to the next escape sequence.
+        let end = rest.find('\x1b').unwrap_or(rest.len());
+
+        match spans.last_mut() {
+            Some((last, text)) if *last == style => text.push_str(&rest[..end]),
+            _ => spans.push((style, rest[..end].to_owned())),
+        }
+
+        rest = &rest[end..];
+    }
+
+    return spans;
+}
+
+/// Apply the parameters of a Select Graphic Rendition (SGR) escape sequence
+/// (e.g. `1;32` in `ESC [ 1 ; 32 m`) to `style`.
+///
+/// An empty parameter is equivalent to `0`, and unknown or malformed
+/// parameters are ignored.
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    const UNDERLINES: Effects = Effects::UNDERLINE
+        .insert(Effects::DOUBLE_UNDERLINE)
+        .insert(Effects::CURLY_UNDERLINE)
+        .insert(Effects::DOTTED_UNDERLINE)
+        .insert(Effects::DASHED_UNDERLINE);
+
+    let mut params = params.split(';');
+
+    while let Some(param) = params.next() {
+        let mut sub_params = param.split(':');
+
+        let code: u16 = match sub_params.next().unwrap_or("") {
+            "" => 0,
+            code => match code.parse() {
+                Ok(code) => code,
+                Err(_) => continue,
+            },
+        };
+
+        let sub_params: Vec<&str> = sub_params.collect();
+
+        let effects = style.get_effects();
+
+        // Extended colors consume the parameters that follow them, e.g. the
+        // `5;n` in `38;5;n`.
+        let mut color = || extended_color(&sub_params, &mut params);
+
+        style = match code {
+            0 => Style::new(),
+            1 => style.effects(effects.insert(Effects::BOLD)),
+            2 => style.effects(effects.insert(Effects::DIMMED)),
+            3 => style.effects(effects.insert(Effects::ITALIC)),
+            4 => {
+                // `4:0` disables underlining, and `4:n` selects an
+                // underline variant.
+                let underline = match sub_params.first().copied() {
+                    None => Effects::UNDERLINE,
+                    Some("0") => Effects::new(),
+                    Some("2") => Effects::DOUBLE_UNDERLINE,
+                    Some("3") => Effects::CURLY_UNDERLINE,
+                    Some("4") => Effects::DOTTED_UNDERLINE,
+                    Some("5") => Effects::DASHED_UNDERLINE,
+                    Some(_) => Effects::UNDERLINE,
+                };
+                style.effects(effects.remove(UNDERLINES).insert(underline))
+            },
+            5 => style.effects(effects.insert(Effects::BLINK)),
+            7 => style.effects(effects.insert(Effects::INVERT)),
+            8 => style.effects(effects.insert(Effects::HIDDEN)),
+            9 => style.effects(effects.insert(Effects::STRIKETHROUGH)),
+            21 => style.effects(effects.insert(Effects::DOUBLE_UNDERLINE)),
+            22 => {
+                style.effects(effects.remove(Effects::BOLD | Effects::DIMMED))
+            },
+            23 => style.effects(effects.remove(Effects::ITALIC)),
+            24 => style.effects(effects.remove(UNDERLINES)),
+            25 => style.effects(effects.remove(Effects::BLINK)),
+            27 => style.effects(effects.remove(Effects::INVERT)),
+            28 => style.effects(effects.remove(Effects::HIDDEN)),
+            29 => style.effects(effects.remove(Effects::STRIKETHROUGH)),
+            30..=37 => style.fg_color(Some(ansi_color(code - 30))),
+            38 => style.fg_color(color()),
+            39 => style.fg_color(None),
+            40..=47 => style.bg_color(Some(ansi_color(code - 40))),
+            48 => style.bg_color(color()),
+            49 => style.bg_color(None),
+            58 => style.underline_color(color()),
+            59 => style.underline_color(None),
+            90..=97 => style.fg_color(Some(ansi_color(code - 90 + 8))),
+            100..=107 => style.bg_color(Some(ansi_color(code - 100 + 8))),
+            _ => style,
+        };
+    }
+
+    return style;
+}
+
+/// Get the color with the given index (`0` to `15`) in the 16 color ANSI
+/// palette.
+fn ansi_color(index: u16) -> Color {
+    let color = Ansi256Color(index as u8);
 
-    if let Some(long_about) = command.get_long_about() {
-        writeln!(buffer, "{}\n", long_about)?;
-    } else if let Some(about) = command.get_about() {
-        writeln!(buffer, "{}\n", about)?;
+    match color.into_ansi() {
+        Some(ansi) => Color::Ansi(ansi),
+        None => Color::Ansi256(color),
     }
+}
 
-    if let Some(help) = command.get_before_long_help() {
-        writeln!(buffer, "{}\n", help)?;
-    } else if let Some(help) = command.get_before_help() {
-        writeln!(buffer, "{}\n", help)?;
+/// Parse the parameters of an extended (256 color or RGB) SGR color, e.g.
+/// `5;n` or `2;r;g;b`.
+///
+/// The color may instead be given by the colon separated `sub_params` of the
+/// color parameter, e.g. `5:n` or `2:r:g:b`, optionally including a color
+/// space id, e.g. `2::r:g:b`.
+fn extended_color<'a>(
+    sub_params: &[&str],
+    params: &mut impl Iterator<Item = &'a str>,
+) -> Option<Color> {
+    if let Some((&mode, values)) = sub_params.split_first() {
+        let parse = |i: usize| values.get(i)?.parse::<u8>().ok();
+        // Skip the color space id, if any.
+        let rgb = values.len().saturating_sub(3);
+
+        return match mode {
+            "5" => Some(Color::Ansi256(Ansi256Color(parse(0)?))),
+            "2" => Some(Color::Rgb(RgbColor(
+                parse(rgb)?,
+                parse(rgb + 1)?,
+                parse(rgb + 2)?,
+            ))),
+            _ => None,
+        };
     }
 
-    writeln!(
-        buffer,
-        "**Usage:** `{}{}`\n",
-        if parent_command_path.is_empty() {
-            String::new()
-        } else {
-            let mut s = parent_command_path.join(" ");
-            s.push_str(" ");
-            s
-        },
-        command
-            .clone()
-            .render_usage()
-            .to_string()
-            .replace("Usage: ", "")
-    )?;
+    let mut next = || params.next().and_then(|param| param.parse::<u8>().ok());
 
-    if let Some(help) = command.get_after_long_help() {
-        writeln!(buffer, "{}\n", help)?;
-    } else if let Some(help) = command.get_after_help() {
-        writeln!(buffer, "{}\n", help)?;
+    match next()? {
+        5 => Some(Color::Ansi256(Ansi256Color(next()?))),
+        2 => Some(Color::Rgb(RgbColor(next()?, next()?, next()?))),
+        _ => None,
     }
+}
 
-    //----------------------------------
-    // Subcommands
-    //----------------------------------
-
-    if command.get_subcommands().next().is_some() {
-        writeln!(buffer, "###### **Subcommands:**\n")?;
+//======================================
+// Links
+//======================================
 
-        for subcommand in command.get_subcommands() {
-            if subcommand.is_hide_set() {
-                continue;
-            }
+/// Determines how links to the documentation of a command are formed.
+#[derive(Clone, Copy)]
+enum CommandLinks<'a> {
+    /// All commands are documented in a single document, so link to the
+    /// heading of the command section, using the anchor of each command path.
+    Anchor(&'a HashMap<Vec<String>, String>),
+    /// Each command is documented on a separate page, so link to the page of
+    /// the command, relative to the page currently being written.
+    Page { current_page: &'a str },
+}
 
-            let title_name = get_canonical_name(subcommand);
+impl CommandLinks<'_> {
+    /// Get the link target for the documentation of the command at
+    /// `command_path`.
+    fn href(&self, command_path: &[String]) -> String {
+        match self {
+            CommandLinks::Anchor(anchors) => {
+                format!("#{}", anchors[command_path])
+            },
+            CommandLinks::Page { current_page } => {
+                relative_path(current_page, &command_page_path(command_path))
+            },
+        }
+    }
 
-            let about = match subcommand.get_about() {
-                Some(about) => about.to_string(),
-                None => String::new(),
-            };
+    /// Get the id of the "Global Options" section of the documentation of the
+    /// command at `command_path`.
+    fn global_options_anchor(&self, command_path: &[String]) -> String {
+        match self {
+            CommandLinks::Anchor(anchors) => {
+                global_options_anchor(&anchors[command_path])
+            },
+            // Each page documents a single command.
+            CommandLinks::Page { .. } => "global-options".to_owned(),
+        }
+    }
 
-            writeln!(buffer, "* `{title_name}` — {about}",)?;
+    /// Get the link target for the "Global Options" section of the
+    /// documentation of the command at `command_path`.
+    fn global_options_href(&self, command_path: &[String]) -> String {
+        let anchor = self.global_options_anchor(command_path);
+
+        match self {
+            CommandLinks::Anchor(_) => format!("#{anchor}"),
+            CommandLinks::Page { current_page } => format!(
+                "{}#{anchor}",
+                relative_path(current_page, &command_page_path(command_path))
+            ),
         }
+    }
+}
 
-        write!(buffer, "\n")?;
+/// Get the id of the "Global Options" section of the command whose section has
+/// the id `command_anchor`.
+fn global_options_anchor(command_anchor: &str) -> String {
+    return format!("{command_anchor}-global-options");
+}
+
+/// Get the id to link to for the global arguments in `inherited_globals`
+/// declared by the command at `parent_path`, given the `anchors` of the
+/// command sections: the id of its "Global Options" section if it has one
+/// (see [`has_global_options_section()`]), and otherwise the id of its section.
+fn inherited_globals_anchor(
+    anchors: &HashMap<Vec<String>, String>,
+    inherited_globals: &[GlobalArg],
+    parent_path: &[String],
+) -> String {
+    if has_global_options_section(inherited_globals, parent_path) {
+        return global_options_anchor(&anchors[parent_path]);
     }
 
-    //----------------------------------
-    // Arguments
-    //----------------------------------
+    return anchors[parent_path].clone();
+}
 
-    if command.get_positionals().next().is_some() {
-        writeln!(buffer, "###### **Arguments:**\n")?;
+/// Slugify `text` the way GitHub does when generating heading anchors.
+fn github_slug(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            _ if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
 
-        for pos_arg in command.get_positionals() {
-            write_arg_markdown(buffer, pos_arg)?;
+/// Get the unique heading anchor of each documented command in `command`,
+/// keyed by command path.
+fn command_anchors(
+    command: &clap::Command,
+    anchor_style: AnchorStyle,
+) -> HashMap<Vec<String>, String> {
+    let mut anchors = HashMap::new();
+    // Anchors that have already been assigned to a command.
+    let mut used = HashSet::new();
+
+    // Commands are visited in the same order their sections are written, so
+    // that, like Markdown renderers do, the first of several headings with
+    // the same slug keeps the unsuffixed anchor. Hidden commands have no
+    // section, and so no anchor.
+    //
+    // Note that only command headings are considered. The slugs of the other
+    // headings in the document (e.g. "Options:") are constant, and could only
+    // collide with the heading of a root command with the same name.
+    for node in documented_commands(command) {
+        let slug = anchor_style.slug(&node.command_path);
+
+        let mut anchor = slug.clone();
+        let mut suffix = 0;
+        while !used.insert(anchor.clone()) {
+            suffix += 1;
+            anchor = format!("{slug}-{suffix}");
         }
 
-        write!(buffer, "\n")?;
+        anchors.insert(node.command_path, anchor);
     }
 
-    //----------------------------------
-    // Options
-    //----------------------------------
+    anchors
+}
 
-    let non_pos: Vec<_> = command
-        .get_arguments()
-        .filter(|arg| !arg.is_positional() && !arg.is_hide_set())
-        .collect();
+/// Get the path of the page documenting the command at `command_path`,
+/// relative to the root of the pages.
+///
+/// The root command is documented by `index.md`, and subcommands are
+/// documented by a page at a path containing the names of their parent
+/// commands, e.g. `tool/remote/add.md`.
+fn command_page_path(command_path: &[String]) -> String {
+    match command_path {
+        [] => unreachable!("command path is empty"),
+        [_root] => "index.md".to_owned(),
+        _ => format!("{}.md", command_path.join("/")),
+    }
+}
 
-    if !non_pos.is_empty() {
-        writeln!(buffer, "###### **Options:**\n")?;
+/// Get the relative path from the file at `from` to the file at `to`, where
+/// both paths are `/`-separated and relative to the same directory.
+fn relative_path(from: &str, to: &str) -> String {
+    let from_dirs: Vec<&str> = match from.rsplit_once('/') {
+        Some((dir, _)) => dir.split('/').collect(),
+        None => Vec::new(),
+    };
+    let to_components: Vec<&str> = to.split('/').collect();
 
-        for arg in non_pos {
-            write_arg_markdown(buffer, arg)?;
-        }
+    let common = from_dirs
+        .iter()
+        .zip(&to_components)
+        .take_while(|(a, b)| a == b)
+        .count();
 
-        write!(buffer, "\n")?;
-    }
+    let mut components: Vec<&str> = vec![".."; from_dirs.len() - common];
+    components.extend(&to_components[common..]);
 
-    //----------------------------------
-    // Recurse to write subcommands
-    //----------------------------------
+    components.join("/")
+}
 
-    // Include extra space between commands. This is purely for the benefit of
-    // anyone reading the source .md file.
-    write!(buffer, "\n\n")?;
+//======================================
+// Writer adapters
+//======================================
 
-    for subcommand in command.get_subcommands() {
-        build_command_markdown(
-            buffer,
-            command_path.clone(),
-            subcommand,
-            depth + 1,
-        )?;
+/// Adapts a generic [`fmt::Write`] sink for use with the `&mut dyn Write`
+/// based Markdown writing functions.
+struct FmtWriter<'w, W: fmt::Write + ?Sized>(&'w mut W);
+
+impl<W: fmt::Write + ?Sized> fmt::Write for FmtWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
     }
+}
 
-    Ok(())
+/// Adapts an [`io::Write`] sink for use with the `&mut dyn Write` based
+/// Markdown writing functions.
+///
+/// [`fmt::Error`] carries no information, so the underlying I/O error is
+/// saved in `error` so that it can be returned to the caller.
+struct IoWriter<'w, W: io::Write + ?Sized> {
+    inner: &'w mut W,
+    error: Option<io::Error>,
 }
 
-fn write_arg_markdown(buffer: &mut String, arg: &clap::Arg) -> fmt::Result {
-    // Markdown list item
-    write!(buffer, "* ")?;
+impl<W: io::Write + ?Sized> fmt::Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
 
-    let value_name: String = match arg.get_value_names() {
-        // TODO: What if multiple names are provided?
-        Some([name, ..]) => name.as_str().to_owned(),
-        Some([]) => unreachable!(
-            "clap Arg::get_value_names() returned Some(..) of empty list"
-        ),
-        None => arg.get_id().to_string().to_ascii_uppercase(),
-    };
+//======================================
+// Utilities
+//======================================
 
-    match (arg.get_short(), arg.get_long()) {
-        (Some(short), Some(long)) => {
-            if arg.get_action().takes_values() {
-                write!(buffer, "`-{short}`, `--{long} <{value_name}>`")?
-            } else {
-                write!(buffer, "`-{short}`, `--{long}`")?
-            }
-        },
-        (Some(short), None) => {
-            if arg.get_action().takes_values() {
-                write!(buffer, "`-{short} <{value_name}>`")?
-            } else {
-                write!(buffer, "`-{short}`")?
-            }
+/// Get the number of values `arg` takes.
+///
+/// clap only sets a default for `Arg::get_num_args()` when the command is
+/// built, so the default is inferred here the same way for unbuilt commands.
+fn arg_num_args(arg: &clap::Arg) -> ValueRange {
+    if let Some(num_args) = arg.get_num_args() {
+        return num_args;
+    }
+
+    match arg.get_value_names() {
+        Some(names) if names.len() > 1 => ValueRange::new(names.len()),
+        _ if arg.get_action().takes_values() => ValueRange::SINGLE,
+        _ => ValueRange::EMPTY,
+    }
+}
+
+/// Format the notation for the value(s) taken by `arg`, following the same
+/// conventions as the usage and help output of clap.
+///
+/// For positional arguments, this is the complete notation, e.g. `<FILE>`,
+/// `[NAME]`, or `<FILE>...`. For other arguments, this is the suffix that
+/// follows the flag name, e.g. ` <FILE>`, ` [<WHEN>]`, or `=<N>`, or empty if
+/// `arg` takes no values.
+fn arg_value_notation(arg: &clap::Arg) -> String {
+    let num_args = arg_num_args(arg);
+
+    if !arg.is_positional() && !num_args.takes_values() {
+        return String::new();
+    }
+
+    let value_names: Vec<String> = match arg.get_value_names() {
+        // If there is a single value name, repeat it for each required value,
+        // e.g. `<X> <X>`.
+        Some([name]) => {
+            vec![name.as_str().to_owned(); num_args.min_values().max(1)]
         },
-        (None, Some(long)) => {
-            if arg.get_action().takes_values() {
-                write!(buffer, "`--{} <{value_name}>`", long)?
-            } else {
-                write!(buffer, "`--{}`", long)?
-            }
+        // Otherwise, each value has its own name, e.g. `<START> <END>`.
+        Some(names) => {
+            names.iter().map(|name| name.as_str().to_owned()).collect()
         },
-        (None, None) => {
-            debug_assert!(arg.is_positional(), "unexpected non-positional Arg with neither short nor long name: {arg:?}");
+        None => vec![
+            arg.get_id().to_string().to_ascii_uppercase();
+            num_args.min_values().max(1)
+        ],
+    };
 
-            write!(buffer, "`<{value_name}>`",)?;
-        },
+    debug_assert!(
+        !value_names.is_empty(),
+        "clap Arg::get_value_names() returned Some(..) of empty list"
+    );
+
+    let is_optional_value = num_args.min_values() == 0;
+
+    let mut values: Vec<String> = Vec::new();
+
+    for (index, value_name) in value_names.iter().enumerate() {
+        let is_past_min = index >= num_args.min_values();
+
+        let is_optional = if arg.is_positional() {
+            !arg.is_required_set() || is_past_min
+        } else {
+            // An optional value is bracketed as a whole below, so avoid
+            // `[[NAME]]`.
+            !is_optional_value && is_past_min
+        };
+
+        // Each value may itself be a list of values separated by the value
+        // delimiter, e.g. `--tags a,b,c`.
+        let value = match (is_optional, arg.get_value_delimiter()) {
+            (false, None) => format!("<{value_name}>"),
+            (true, None) => format!("[{value_name}]"),
+            (false, Some(delim)) => {
+                format!("<{value_name}>[{delim}<{value_name}>...]")
+            },
+            (true, Some(delim)) => {
+                format!("[{value_name}[{delim}{value_name}...]]")
+            },
+        };
+
+        values.push(value);
     }
 
-    if let Some(help) = arg.get_long_help() {
-        // TODO: Parse formatting in the string
-        buffer.push_str(&indent(&help.to_string(), " — ", "   "))
-    } else if let Some(short_help) = arg.get_help() {
-        writeln!(buffer, " — {short_help}")?;
-    } else {
-        writeln!(buffer)?;
+    let mut notation = values.join(" ");
+
+    if value_names.len() < num_args.max_values()
+        || (arg.is_positional()
+            && matches!(arg.get_action(), clap::ArgAction::Append))
+    {
+        notation.push_str("...");
     }
 
-    //--------------------
-    // Arg default values
-    //--------------------
+    if arg.is_positional() {
+        return notation;
+    }
 
-    if !arg.get_default_values().is_empty() {
-        let default_values: String = arg
-            .get_default_values()
-            .iter()
-            .map(|value| format!("`{}`", value.to_string_lossy()))
-            .collect::<Vec<String>>()
-            .join(", ");
+    match (arg.is_require_equals_set(), is_optional_value) {
+        (true, true) => format!("[={notation}]"),
+        (true, false) => format!("={notation}"),
+        (false, true) => format!(" [{notation}]"),
+        (false, false) => format!(" {notation}"),
+    }
+}
 
-        if arg.get_default_values().len() > 1 {
-            // Plural
-            writeln!(buffer, "\n  Default values: {default_values}")?;
-        } else {
-            // Singular
-            writeln!(buffer, "\n  Default value: {default_values}")?;
-        }
+/// Whether `arg` should be documented.
+///
+/// This matches the arguments shown by clap's long help output.
+fn should_show_arg(arg: &clap::Arg) -> bool {
+    if arg.is_hide_set() {
+        return false;
     }
 
-    //--------------------
-    // Arg possible values
-    //--------------------
+    !arg.is_hide_long_help_set() || arg.is_next_line_help_set()
+}
 
-    let possible_values: Vec<PossibleValue> = arg
-        .get_possible_values()
+/// Get the key used to sort non-positional arguments within a help section.
+///
+/// This matches the order of arguments in clap's help output: arguments are
+/// ordered by their display order, and then by their short or long names.
+fn option_sort_key(arg: &clap::Arg) -> (usize, String) {
+    // Arguments with the same display order are sorted such that:
+    //
+    // 1. Arguments with a short flag come first, with `-C` sorted after `-c`.
+    // 2. Arguments with only a long flag come next.
+    // 3. Arguments with neither come last, sorted by id.
+    let key = if let Some(short) = arg.get_short() {
+        let mut key = short.to_ascii_lowercase().to_string();
+        key.push(if short.is_ascii_lowercase() { '0' } else { '1' });
+        key
+    } else if let Some(long) = arg.get_long() {
+        long.to_owned()
+    } else {
+        format!("{{{}", arg.get_id())
+    };
+
+    (arg.get_display_order(), key)
+}
+
+/// Get the visible aliases of `command`.
+///
+/// This includes aliases for the command name, as well as any aliases for the
+/// short and long flags of a flag subcommand.
+fn command_aliases(command: &clap::Command) -> Vec<String> {
+    let names = command.get_visible_aliases().map(|name| name.to_owned());
+
+    let short_flags = command
+        .get_visible_short_flag_aliases()
+        .map(|short| format!("-{short}"));
+
+    let long_flags = command
+        .get_visible_long_flag_aliases()
+        .map(|long| format!("--{long}"));
+
+    names.chain(short_flags).chain(long_flags).collect()
+}
+
+/// Get the visible short and long aliases of `arg`.
+fn arg_aliases(arg: &clap::Arg) -> Vec<String> {
+    let shorts = arg
+        .get_visible_short_aliases()
+        .unwrap_or_default()
         .into_iter()
-        .filter(|pv| !pv.is_hide_set())
-        .collect();
+        .map(|short| format!("-{short}"));
+
+    let longs = arg
+        .get_visible_aliases()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|long| format!("--{long}"));
+
+    shorts.chain(longs).collect()
+}
+
+/// A global argument, and the command that declares it.
+#[derive(Clone)]
+struct GlobalArg {
+    /// Path of the command that declares the argument.
+    command_path: Vec<String>,
+    id: clap::Id,
+    /// Whether the argument is documented in the "Global Options" section of
+    /// the command that declares it.
+    in_global_options: bool,
+}
+
+/// Get the global arguments inherited by the subcommands of `command`: those
+/// inherited by `command` itself, and the visible global arguments declared by
+/// `command`.
+fn subcommand_globals(
+    inherited_globals: &[GlobalArg],
+    command_path: &[String],
+    command: &clap::Command,
+) -> Vec<GlobalArg> {
+    let mut globals = inherited_globals.to_vec();
 
-    // Print possible values for options that take a value, but not for flags
-    // that can only be either present or absent and do not take a value.
-    if !possible_values.is_empty()
-        && !matches!(arg.get_action(), clap::ArgAction::SetTrue)
+    for arg in command
+        .get_arguments()
+        .filter(|arg| arg.is_global_set())
+        .filter(|arg| should_show_arg(arg))
+        .filter(|arg| !is_inherited_global(inherited_globals, arg))
     {
-        let any_have_help: bool =
-            possible_values.iter().any(|pv| pv.get_help().is_some());
-
-        if any_have_help {
-            // If any of the possible values have help text, print them
-            // as a separate item in a bulleted list, and include the
-            // help text for those that have it. E.g.:
-            //
-            //     Possible values:
-            //     - `value1`:
-            //       The help text
-            //     - `value2`
-            //     - `value3`:
-            //       The help text
-
-            let text: String = possible_values
-                .iter()
-                .map(|pv| match pv.get_help() {
-                    Some(help) => {
-                        format!("  - `{}`:\n    {}\n", pv.get_name(), help)
-                    },
-                    None => format!("  - `{}`\n", pv.get_name()),
-                })
-                .collect::<Vec<String>>()
-                .join("");
+        globals.push(GlobalArg {
+            command_path: command_path.to_vec(),
+            id: arg.get_id().clone(),
+            in_global_options: is_global_options_arg(arg),
+        });
+    }
 
-            writeln!(buffer, "\n  Possible values:\n{text}")?;
-        } else {
-            // If none of the possible values have any documentation, print
-            // them all inline on a single line.
-            let text: String = possible_values
-                .iter()
-                // TODO: Show PossibleValue::get_help(), and PossibleValue::get_name_and_aliases().
-                .map(|pv| format!("`{}`", pv.get_name()))
-                .collect::<Vec<String>>()
-                .join(", ");
+    return globals;
+}
 
-            writeln!(buffer, "\n  Possible values: {text}\n")?;
-        }
+/// Whether `arg` is a global argument propagated from a parent command.
+fn is_inherited_global(
+    inherited_globals: &[GlobalArg],
+    arg: &clap::Arg,
+) -> bool {
+    inherited_globals
+        .iter()
+        .any(|global| global.id == *arg.get_id())
+}
+
+/// Get the visible arguments of `command` that cannot be used together with
+/// `arg`.
+///
+/// Conflicts are symmetric, so this includes both the arguments that `arg`
+/// conflicts with, and the arguments that conflict with `arg`. The arguments
+/// of an argument group that does not allow `multiple` arguments also conflict
+/// with each other.
+fn arg_conflicts<'c>(
+    command: &'c clap::Command,
+    arg: &clap::Arg,
+) -> Vec<&'c clap::Arg> {
+    let conflicts_with = |a: &clap::Arg, b: &clap::Arg| {
+        command
+            .get_arg_conflicts_with(a)
+            .iter()
+            .any(|other| other.get_id() == b.get_id())
+    };
+
+    let exclusive_group_members: Vec<&clap::Id> = command
+        .get_groups()
+        .filter(|group| !(*group).clone().is_multiple())
+        .filter(|group| group.get_args().any(|id| id == arg.get_id()))
+        .flat_map(|group| group.get_args())
+        .collect();
+
+    return command
+        .get_arguments()
+        .filter(|other| other.get_id() != arg.get_id())
+        .filter(|other| should_show_arg(other))
+        .filter(|other| {
+            conflicts_with(arg, other)
+                || conflicts_with(other, arg)
+                || exclusive_group_members.contains(&other.get_id())
+        })
+        .collect();
+}
+
+/// Get the name of the environment variable bound to `arg`, unless it is
+/// hidden.
+#[cfg(feature = "env")]
+fn arg_env(arg: &clap::Arg) -> Option<String> {
+    if arg.is_hide_env_set() {
+        return None;
     }
 
-    Ok(())
+    arg.get_env().map(|env| env.to_string_lossy().into_owned())
+}
+
+/// Get the name of the environment variable bound to `arg`, unless it is
+/// hidden.
+///
+/// Environment variables can only be bound when clap's `env` feature is
+/// enabled.
+#[cfg(not(feature = "env"))]
+fn arg_env(_arg: &clap::Arg) -> Option<String> {
+    None
+}
+
+/// Get the name `arg` is referred to by on the command line, e.g. `--foo`,
+/// `-f`, or `<FOO>` for positional arguments.
+fn arg_display_name(arg: &clap::Arg) -> String {
+    match (arg.get_long(), arg.get_short()) {
+        (Some(long), _) => format!("--{long}"),
+        (None, Some(short)) => format!("-{short}"),
+        (None, None) => {
+            let value_name = match arg.get_value_names() {
+                Some([name, ..]) => name.as_str().to_owned(),
+                _ => arg.get_id().to_string().to_ascii_uppercase(),
+            };
+
+            format!("<{value_name}>")
+        },
+    }
 }
 
 /// Utility function to get the canonical name of a command.
@@ -576,6 +3080,159 @@ fn get_canonical_name(command: &clap::Command) -> String {
         .unwrap_or_else(|| command.get_name().to_owned())
 }
 
+/// Format `items` as a comma-separated list of inline code, e.g.
+/// `` `-v`, `--verbose` ``.
+fn code_list(items: &[String]) -> String {
+    return items
+        .iter()
+        .map(|item| format!("`{item}`"))
+        .collect::<Vec<String>>()
+        .join(", ");
+}
+
+/// Replace each `{{variable}}` placeholder in `template` with the value of
+/// that variable in `variables`.
+///
+/// Whitespace around the variable name is ignored. Placeholders of unknown
+/// variables are left unchanged.
+fn render_template(template: &str, variables: &[(&str, &str)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}").map(|end| end + 2) else {
+            break;
+        };
+
+        output.push_str(&rest[..start]);
+
+        let placeholder = &rest[start..start + len];
+        let name = placeholder[2..placeholder.len() - 2].trim();
+
+        match variables.iter().find(|(variable, _)| *variable == name) {
+            Some((_, value)) => output.push_str(value),
+            None => output.push_str(placeholder),
+        }
+
+        rest = &rest[start + len..];
+    }
+
+    output.push_str(rest);
+
+    return output;
+}
+
+/// Get the date substituted for `{{date}}` placeholders: the
+/// [configured date](MarkdownOptions::date) if set, and otherwise the
+/// [generation date](generation_date).
+///
+/// This is computed once per document, so every part of the document shows
+/// the same date.
+fn document_date(options: &MarkdownOptions) -> String {
+    match options.date {
+        Some(ref date) => date.to_owned(),
+        None => generation_date(),
+    }
+}
+
+/// Get the date the document is generated on, in UTC, formatted as
+/// `YYYY-MM-DD`.
+///
+/// If the `SOURCE_DATE_EPOCH` environment variable is set to a Unix timestamp,
+/// the date of that timestamp is used instead.
+fn generation_date() -> String {
+    let timestamp = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs())
+        });
+
+    let (year, month, day) = civil_from_days(timestamp / 86_400);
+
+    return format!("{year:04}-{month:02}-{day:02}");
+}
+
+/// Convert a number of days since 1970-01-01 to a `(year, month, day)` date in
+/// the proleptic Gregorian calendar.
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // Shift the epoch to 0000-03-01, the start of a 400-year era.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // Months starting from March.
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+
+    return (year, month, day);
+}
+
+/// Format `text` as a double-quoted YAML string.
+fn yaml_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+
+    quoted.push('"');
+
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            _ if c.is_control() => {
+                quoted.push_str(&format!("\\u{:04X}", u32::from(c)))
+            },
+            _ => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+
+    return quoted;
+}
+
+/// Collect the content written by `write` into a [`String`], without trailing
+/// whitespace.
+fn write_to_string(
+    write: impl FnOnce(&mut dyn Write) -> fmt::Result,
+) -> Result<String, fmt::Error> {
+    let mut buffer = String::new();
+
+    write(&mut buffer)?;
+
+    buffer.truncate(buffer.trim_end().len());
+
+    return Ok(buffer);
+}
+
+/// Like [`styled_markdown()`], but returns an empty string if there is no
+/// `text`.
+fn styled_markdown_or_empty(
+    options: &MarkdownOptions,
+    command: &clap::Command,
+    text: Option<&StyledStr>,
+) -> String {
+    match text {
+        Some(text) => styled_markdown(options, command, text),
+        None => String::new(),
+    }
+}
+
 /// Indents non-empty lines. The output always ends with a newline.
 fn indent(s: &str, first: &str, rest: &str) -> String {
     if s.is_empty() {
@@ -615,4 +3272,158 @@ mod test {
         assert_eq!(&indent("", "___", "~~~~"), "\n");
         assert_eq!(&indent("\n", "___", "~~~~"), "\n");
     }
+
+    #[test]
+    fn test_render_template() {
+        use super::render_template;
+
+        let variables = [("name", "tool"), ("version", "1.0")];
+
+        assert_eq!(
+            render_template("{{name}} v{{ version }}", &variables),
+            "tool v1.0"
+        );
+        assert_eq!(
+            render_template("{{unknown}} {{name}}", &variables),
+            "{{unknown}} tool"
+        );
+        assert_eq!(render_template("{{name", &variables), "{{name");
+        assert_eq!(render_template("{ {{name}}}", &variables), "{ tool}");
+    }
+
+    #[test]
+    fn test_civil_from_days() {
+        use super::civil_from_days;
+
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(59), (1970, 3, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(19_889), (2024, 6, 15));
+    }
+
+    #[test]
+    fn test_yaml_string() {
+        use super::yaml_string;
+
+        assert_eq!(yaml_string("plain: text"), r#""plain: text""#);
+        assert_eq!(
+            yaml_string("a \"quoted\" \\path\nline\u{7}"),
+            r#""a \"quoted\" \\path\nline\u0007""#
+        );
+    }
+
+    #[test]
+    fn test_escape_mdx() {
+        use super::escape_mdx;
+
+        assert_eq!(
+            escape_mdx("Use <FILE> or {x}, e.g. `<FILE>` or ``a`{b}``"),
+            "Use \\<FILE> or \\{x\\}, e.g. `<FILE>` or ``a`{b}``"
+        );
+        assert_eq!(escape_mdx("unclosed `<a> span"), "unclosed `\\<a> span");
+    }
+
+    #[test]
+    fn test_relative_path() {
+        use super::relative_path;
+
+        assert_eq!(relative_path("index.md", "a/b.md"), "a/b.md");
+        assert_eq!(relative_path("a/b.md", "a/b/c.md"), "b/c.md");
+        assert_eq!(relative_path("a/b/c.md", "index.md"), "../../index.md");
+        assert_eq!(relative_path("a/b/c.md", "a/d.md"), "../d.md");
+        assert_eq!(relative_path("a/b.md", "a/b.md"), "b.md");
+    }
+
+    #[test]
+    fn test_styled_spans() {
+        use super::styled_spans;
+        use clap::builder::styling::{
+            Ansi256Color, AnsiColor, Color, RgbColor, Style,
+        };
+
+        assert_eq!(styled_spans("plain"), [(Style::new(), "plain".to_owned())]);
+        assert_eq!(
+            styled_spans("a\x1b[1;4mb\x1b[24mc\x1b[0m\x1b[92md\x1b[me"),
+            [
+                (Style::new(), "a".to_owned()),
+                (Style::new().bold().underline(), "b".to_owned()),
+                (Style::new().bold(), "c".to_owned()),
+                (AnsiColor::BrightGreen.on_default(), "d".to_owned()),
+                (Style::new(), "e".to_owned()),
+            ]
+        );
+        assert_eq!(
+            styled_spans("\x1b[38;5;208mé\x1b[48;2;1;2;3mü"),
+            [
+                (
+                    Style::new()
+                        .fg_color(Some(Color::Ansi256(Ansi256Color(208)))),
+                    "é".to_owned()
+                ),
+                (
+                    Style::new()
+                        .fg_color(Some(Color::Ansi256(Ansi256Color(208))))
+                        .bg_color(Some(Color::Rgb(RgbColor(1, 2, 3)))),
+                    "ü".to_owned()
+                ),
+            ]
+        );
+        assert_eq!(
+            styled_spans("\x1b[38:5:208;1ma\x1b[0;48:2::1:2:3mb"),
+            [
+                (
+                    Style::new()
+                        .fg_color(Some(Color::Ansi256(Ansi256Color(208))))
+                        .bold(),
+                    "a".to_owned()
+                ),
+                (
+                    Style::new().bg_color(Some(Color::Rgb(RgbColor(1, 2, 3)))),
+                    "b".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_styled_spans_malformed() {
+        use super::styled_spans;
+        use clap::builder::styling::Style;
+
+        let plain = |text: &str| vec![(Style::new(), text.to_owned())];
+        let bold = |text: &str| vec![(Style::new().bold(), text.to_owned())];
+
+        // Truncated escape sequences at the end of the text are discarded.
+        assert_eq!(styled_spans("a\x1b"), plain("a"));
+        assert_eq!(styled_spans("a\x1b["), plain("a"));
+        assert_eq!(styled_spans("a\x1b[1;3"), plain("a"));
+        assert_eq!(styled_spans("a\x1b]8;;https://example.com"), plain("a"));
+
+        // Malformed sequences are discarded, keeping the text after them.
+        assert_eq!(styled_spans("\x1b[1\x1b[1ma"), bold("a"));
+        assert_eq!(styled_spans("\x1b[1\nm"), plain("\nm"));
+        assert_eq!(styled_spans("a\x1bb"), plain("a"));
+        assert_eq!(styled_spans("\x1b[1;éa"), plain("éa"));
+
+        // Unknown or malformed parameters are ignored, instead of resetting
+        // the style.
+        assert_eq!(styled_spans("\x1b[1m\x1b[300ma"), bold("a"));
+        assert_eq!(styled_spans("\x1b[1m\x1b[<5;2?ma"), bold("a"));
+        assert_eq!(styled_spans("\x1b[1m\x1b[38;5ma"), bold("a"));
+        assert_eq!(styled_spans("\x1b[1m\x1b[38:9:1ma"), bold("a"));
+        assert_eq!(styled_spans("\x1b[1m\x1b[48;2;1;2ma"), bold("a"));
+
+        // Empty parameters reset the style.
+        assert_eq!(styled_spans("\x1b[1m\x1b[ma"), plain("a"));
+        assert_eq!(styled_spans("\x1b[1m\x1b[;4ma"), {
+            vec![(Style::new().underline(), "a".to_owned())]
+        });
+
+        // Other escape sequences are discarded.
+        assert_eq!(styled_spans("\x1b[2Ka\x1b7b"), plain("ab"));
+        assert_eq!(
+            styled_spans("\x1b]8;;https://example.com\x1b\\a\x1b]8;;\x07b"),
+            plain("ab")
+        );
+    }
 }
@@ -0,0 +1,624 @@
+//! Generate man pages (in the roff format) for clap command-line tools.
+//!
+//! One page is generated for each command, named after the command path,
+//! e.g. `tool-remote-add(1)`, with NAME, SYNOPSIS, DESCRIPTION, OPTIONS, and
+//! SEE ALSO sections.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+};
+
+use clap::builder::StyledStr;
+
+use crate::{
+    arg_details, arg_display_name, arg_group_rules, command_aliases,
+    command_arg_sections, command_usage, documented_commands, emphasized_spans,
+    get_canonical_name, global_parents, plain_text, GlobalArg,
+};
+
+//======================================
+// Public API types
+//======================================
+
+/// Options to customize the generated man pages.
+///
+/// Used with [`help_man_pages_custom()`].
+#[non_exhaustive]
+pub struct ManOptions {
+    section: String,
+    date: Option<String>,
+    manual: Option<String>,
+}
+
+impl ManOptions {
+    /// Construct a default instance of `ManOptions`.
+    pub fn new() -> Self {
+        return Self {
+            section: "1".to_owned(),
+            date: None,
+            manual: None,
+        };
+    }
+
+    /// Set the manual section the pages belong to.
+    ///
+    /// The default section is `1` (user commands).
+    pub fn section(mut self, section: String) -> Self {
+        self.section = section;
+
+        return self;
+    }
+
+    /// Set the date shown in the footer of each page, e.g. `2024-06-15`.
+    pub fn date(mut self, date: String) -> Self {
+        self.date = Some(date);
+
+        return self;
+    }
+
+    /// Set the title of the manual shown in the header of each page.
+    pub fn manual(mut self, manual: String) -> Self {
+        self.manual = Some(manual);
+
+        return self;
+    }
+}
+
+impl Default for ManOptions {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+//======================================
+// Public API functions
+//======================================
+
+/// Format the help information for `command` as a man page for each command.
+///
+/// See [`help_man_command_pages_custom()`].
+pub fn help_man_pages<C: clap::CommandFactory>() -> BTreeMap<String, String> {
+    let command = C::command();
+
+    return help_man_command_pages(&command);
+}
+
+/// Format the help information for `command` as a man page for each command,
+/// with custom options.
+///
+/// See [`help_man_command_pages_custom()`].
+pub fn help_man_pages_custom<C: clap::CommandFactory>(
+    options: &ManOptions,
+) -> BTreeMap<String, String> {
+    let command = C::command();
+
+    return help_man_command_pages_custom(&command, options);
+}
+
+/// Format the help information for `command` as a man page for each command.
+///
+/// See [`help_man_command_pages_custom()`].
+pub fn help_man_command_pages(
+    command: &clap::Command,
+) -> BTreeMap<String, String> {
+    return help_man_command_pages_custom(command, &Default::default());
+}
+
+/// Format the help information for `command` as a man page for each command,
+/// with custom options.
+///
+/// The returned map contains an entry for each documented command, from the
+/// file name of the page (e.g. `tool-remote-add.1`) to the roff source of the
+/// page.
+pub fn help_man_command_pages_custom(
+    command: &clap::Command,
+    options: &ManOptions,
+) -> BTreeMap<String, String> {
+    let mut pages = BTreeMap::new();
+
+    // The root command identifies the source of every page, e.g. `tool 1.2.3`.
+    let source = match command.get_version() {
+        Some(version) => format!("{} {version}", get_canonical_name(command)),
+        None => get_canonical_name(command),
+    };
+
+    build_man_pages(&mut pages, options, &source, command)
+        .expect("writing to a String should never fail");
+
+    return pages;
+}
+
+//======================================
+// Man pages
+//======================================
+
+/// Build a man page for `command` and each of its documented subcommands,
+/// inserting them into `pages`.
+fn build_man_pages(
+    pages: &mut BTreeMap<String, String>,
+    options: &ManOptions,
+    source: &str,
+    command: &clap::Command,
+) -> fmt::Result {
+    for node in documented_commands(command) {
+        let mut buffer = String::with_capacity(100);
+
+        write_man_page(
+            &mut buffer,
+            options,
+            source,
+            node.parent_command_path(),
+            &node.inherited_globals,
+            node.command,
+        )?;
+
+        pages.insert(
+            format!("{}.{}", node.command_path.join("-"), options.section),
+            buffer,
+        );
+    }
+
+    Ok(())
+}
+
+/// Write the man page for `command`, not including its subcommands.
+fn write_man_page(
+    buffer: &mut dyn Write,
+    options: &ManOptions,
+    source: &str,
+    // Parent commands of `command`.
+    parent_command_path: &[String],
+    // Global arguments declared by the parent commands of `command`.
+    inherited_globals: &[GlobalArg],
+    command: &clap::Command,
+) -> fmt::Result {
+    // Append the name of `command` to `command_path`.
+    let command_path = {
+        let mut command_path = parent_command_path.to_vec();
+        command_path.push(get_canonical_name(command));
+        command_path
+    };
+
+    let page_name = command_path.join("-");
+
+    //----------------------------------
+    // Title
+    //----------------------------------
+
+    writeln!(
+        buffer,
+        ".TH {} {} {} {} {}",
+        roff_quote(&page_name.to_uppercase()),
+        roff_quote(&options.section),
+        roff_quote(options.date.as_deref().unwrap_or("")),
+        roff_quote(source),
+        roff_quote(options.manual.as_deref().unwrap_or("")),
+    )?;
+
+    //----------------------------------
+    // NAME
+    //----------------------------------
+
+    writeln!(buffer, ".SH NAME")?;
+
+    // The NAME section is used by tools like `apropos`, and should be a
+    // single line.
+    match command.get_about() {
+        Some(about) => {
+            let about = plain_text(about);
+            let summary = about.lines().next().unwrap_or("").trim();
+
+            writeln!(
+                buffer,
+                "{} \\- {}",
+                roff_escape(&page_name),
+                roff_escape(summary)
+            )?;
+        },
+        None => writeln!(buffer, "{}", roff_escape(&page_name))?,
+    }
+
+    //----------------------------------
+    // SYNOPSIS
+    //----------------------------------
+
+    writeln!(buffer, ".SH SYNOPSIS")?;
+
+    let usage = command_usage(parent_command_path, command);
+    let command_name = command_path.join(" ");
+
+    match usage.strip_prefix(&command_name) {
+        Some(rest) => writeln!(
+            buffer,
+            "\\fB{}\\fR{}",
+            roff_escape(&command_name),
+            roff_escape(rest)
+        )?,
+        None => writeln!(buffer, "{}", roff_escape(&usage))?,
+    }
+
+    //----------------------------------
+    // DESCRIPTION
+    //----------------------------------
+
+    let mut description: Vec<String> = Vec::new();
+
+    if let Some(long_about) = command.get_long_about() {
+        description.extend(styled_roff(command, long_about));
+    } else if let Some(about) = command.get_about() {
+        description.extend(styled_roff(command, about));
+    }
+
+    if let Some(help) = command.get_before_long_help() {
+        description.extend(styled_roff(command, help));
+    } else if let Some(help) = command.get_before_help() {
+        description.extend(styled_roff(command, help));
+    }
+
+    let aliases = command_aliases(command);
+
+    if !aliases.is_empty() {
+        description.push(format!(
+            "{}: {}",
+            if aliases.len() > 1 {
+                "Aliases"
+            } else {
+                "Alias"
+            },
+            bold_list(&aliases)
+        ));
+    }
+
+    if let Some(help) = command.get_after_long_help() {
+        description.extend(styled_roff(command, help));
+    } else if let Some(help) = command.get_after_help() {
+        description.extend(styled_roff(command, help));
+    }
+
+    if !description.is_empty() {
+        writeln!(buffer, ".SH DESCRIPTION")?;
+        writeln!(buffer, "{}", description.join("\n.PP\n"))?;
+    }
+
+    //----------------------------------
+    // COMMANDS
+    //----------------------------------
+
+    let subcommands: Vec<&clap::Command> = command
+        .get_subcommands()
+        .filter(|subcommand| !subcommand.is_hide_set())
+        .collect();
+
+    if !subcommands.is_empty() {
+        writeln!(buffer, ".SH COMMANDS")?;
+
+        for subcommand in &subcommands {
+            let mut subcommand_path = command_path.clone();
+            subcommand_path.push(get_canonical_name(subcommand));
+
+            writeln!(buffer, ".TP")?;
+            writeln!(
+                buffer,
+                "\\fB{}\\fR({})",
+                roff_escape(&subcommand_path.join("-")),
+                roff_escape(&options.section)
+            )?;
+
+            if let Some(about) = subcommand.get_about() {
+                writeln!(
+                    buffer,
+                    "{}",
+                    styled_roff(subcommand, about).join("\n.IP\n")
+                )?;
+            }
+        }
+    }
+
+    //----------------------------------
+    // OPTIONS
+    //----------------------------------
+
+    let sections = command_arg_sections(command, inherited_globals);
+    let parents = global_parents(inherited_globals);
+    let groups = arg_group_rules(command);
+
+    if !sections.is_empty() || !parents.is_empty() || !groups.is_empty() {
+        writeln!(buffer, ".SH OPTIONS")?;
+    }
+
+    // The default "Options" section doesn't need its own subheading if it's
+    // the only section.
+    let is_only_options = matches!(sections.as_slice(), [section] if section.heading == "Options");
+
+    for section in sections {
+        if !is_only_options {
+            writeln!(buffer, ".SS {}", roff_quote(section.heading))?;
+        }
+
+        for arg in section.args {
+            write_arg_man(buffer, command, arg)?;
+        }
+    }
+
+    // Refer to the global options inherited from parent commands, instead of
+    // repeating them in every subcommand.
+    for parent_path in parents {
+        writeln!(buffer, ".PP")?;
+        writeln!(
+            buffer,
+            "This command also accepts the global options of \\fB{}\\fR({}).",
+            roff_escape(&parent_path.join("-")),
+            roff_escape(&options.section)
+        )?;
+    }
+
+    if !groups.is_empty() {
+        writeln!(buffer, ".SS \"Argument Groups\"")?;
+
+        for group in groups {
+            let members: Vec<String> =
+                group.members.into_iter().map(arg_display_name).collect();
+
+            writeln!(buffer, ".IP \\(bu 2")?;
+            writeln!(
+                buffer,
+                "\\fB{}\\fR \\- {} {} {} be given",
+                roff_escape(group.id),
+                group.rule,
+                bold_list(&members),
+                group.verb,
+            )?;
+        }
+    }
+
+    //----------------------------------
+    // SEE ALSO
+    //----------------------------------
+
+    let mut see_also: Vec<String> = Vec::new();
+
+    if !parent_command_path.is_empty() {
+        see_also.push(parent_command_path.join("-"));
+    }
+
+    for subcommand in &subcommands {
+        let mut subcommand_path = command_path.clone();
+        subcommand_path.push(get_canonical_name(subcommand));
+
+        see_also.push(subcommand_path.join("-"));
+    }
+
+    if !see_also.is_empty() {
+        writeln!(buffer, ".SH \"SEE ALSO\"")?;
+        writeln!(
+            buffer,
+            "{}",
+            see_also
+                .iter()
+                .map(|page| format!(
+                    "\\fB{}\\fR({})",
+                    roff_escape(page),
+                    roff_escape(&options.section)
+                ))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_arg_man(
+    buffer: &mut dyn Write,
+    command: &clap::Command,
+    arg: &clap::Arg,
+) -> fmt::Result {
+    let details = arg_details(command, arg);
+
+    //--------------------
+    // Arg names
+    //--------------------
+
+    writeln!(buffer, ".TP")?;
+
+    // Only the flags are bold, and not the value notation that follows them.
+    write!(
+        buffer,
+        "{}{}",
+        bold_list(&details.flags),
+        roff_escape(&details.value_notation)
+    )?;
+
+    if !details.notes.is_empty() {
+        write!(buffer, " ({})", details.notes.join(", "))?;
+    }
+
+    writeln!(buffer)?;
+
+    //--------------------
+    // Arg details
+    //--------------------
+
+    // Each paragraph of the description of the argument.
+    let mut paragraphs: Vec<String> = Vec::new();
+
+    if let Some(help) = details.help {
+        paragraphs.extend(styled_roff(command, help));
+    }
+
+    if !details.aliases.is_empty() {
+        paragraphs.push(format!(
+            "{}: {}",
+            if details.aliases.len() > 1 {
+                "Aliases"
+            } else {
+                "Alias"
+            },
+            bold_list(&details.aliases)
+        ));
+    }
+
+    if details.exclusive {
+        paragraphs.push("Cannot be used with any other arguments.".to_owned());
+    } else if !details.conflicts.is_empty() {
+        paragraphs.push(format!(
+            "Cannot be used with {}.",
+            bold_list(&details.conflicts)
+        ));
+    }
+
+    if let Some(env) = &details.env {
+        paragraphs.push(format!(
+            "Environment variable: \\fB{}\\fR",
+            roff_escape(env)
+        ));
+    }
+
+    if !details.default_values.is_empty() {
+        paragraphs.push(format!(
+            "Default {}: {}",
+            if details.default_values.len() > 1 {
+                "values"
+            } else {
+                "value"
+            },
+            bold_list(&details.default_values)
+        ));
+    }
+
+    if !paragraphs.is_empty() {
+        writeln!(buffer, "{}", paragraphs.join("\n.IP\n"))?;
+    }
+
+    //--------------------
+    // Arg possible values
+    //--------------------
+
+    if !details.possible_values.is_empty() {
+        if !paragraphs.is_empty() {
+            writeln!(buffer, ".IP")?;
+        }
+
+        writeln!(buffer, "Possible values:")?;
+        writeln!(buffer, ".RS")?;
+
+        for pv in &details.possible_values {
+            writeln!(buffer, ".IP \\(bu 2")?;
+            write!(buffer, "\\fB{}\\fR", roff_escape(pv.get_name()))?;
+
+            if let Some(help) = pv.get_help() {
+                write!(buffer, ": {}", styled_roff(command, help).join(" "))?;
+            }
+
+            writeln!(buffer)?;
+        }
+
+        writeln!(buffer, ".RE")?;
+    }
+
+    Ok(())
+}
+
+//======================================
+// Utilities
+//======================================
+
+/// Convert styled help text of `command` to roff paragraphs, splitting the
+/// text into paragraphs at blank lines.
+///
+/// Literal and strong text is shown in bold, and emphasized text is shown in
+/// italics.
+fn styled_roff(command: &clap::Command, text: &StyledStr) -> Vec<String> {
+    let mut roff = String::new();
+
+    for (emphasis, text) in emphasized_spans(command, text) {
+        let font = if emphasis.literal || emphasis.strong {
+            "\\fB"
+        } else if emphasis.emphasis {
+            "\\fI"
+        } else {
+            ""
+        };
+
+        // Reset the font at the end of each line, so that no font change
+        // carries over a paragraph break.
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 {
+                roff.push('\n');
+            }
+
+            if font.is_empty() || line.trim().is_empty() {
+                roff.push_str(&roff_escape(line));
+                continue;
+            }
+
+            roff.push_str(font);
+            roff.push_str(&roff_escape(line));
+            roff.push_str("\\fR");
+        }
+    }
+
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut paragraph: Vec<String> = Vec::new();
+
+    for line in roff.lines().chain([""]) {
+        let line = line.trim();
+
+        if line.is_empty() {
+            if !paragraph.is_empty() {
+                paragraphs.push(paragraph.join("\n"));
+                paragraph.clear();
+            }
+        } else if line.starts_with(['.', '\'']) {
+            // Lines starting with `.` or `'` would be interpreted as requests.
+            paragraph.push(format!("\\&{line}"));
+        } else {
+            paragraph.push(line.to_owned());
+        }
+    }
+
+    return paragraphs;
+}
+
+/// Format `items` as a comma-separated list of bold text.
+fn bold_list(items: &[String]) -> String {
+    return items
+        .iter()
+        .map(|item| format!("\\fB{}\\fR", roff_escape(item)))
+        .collect::<Vec<String>>()
+        .join(", ");
+}
+
+/// Escape characters in `text` that have a special meaning in roff.
+///
+/// `text` must be a single line, or the lines of a paragraph.
+fn roff_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for (index, line) in text.split('\n').enumerate() {
+        if index > 0 {
+            escaped.push('\n');
+        }
+
+        // Lines starting with `.` or `'` would be interpreted as requests.
+        if line.starts_with(['.', '\'']) {
+            escaped.push_str("\\&");
+        }
+
+        for c in line.chars() {
+            match c {
+                '\\' => escaped.push_str("\\e"),
+                '-' => escaped.push_str("\\-"),
+                _ => escaped.push(c),
+            }
+        }
+    }
+
+    return escaped;
+}
+
+/// Quote `text` for use as an argument of a roff request, like `.TH`.
+fn roff_quote(text: &str) -> String {
+    return format!("\"{}\"", roff_escape(text).replace('"', "\\(dq"));
+}
@@ -0,0 +1,249 @@
+//! A structured model of the documentation generated for a command.
+//!
+//! The model contains the same content as the generated Markdown, and is built
+//! using the same rules: hidden commands, arguments, and possible values are
+//! omitted, and global arguments are only included in the command that
+//! declares them. Help text is plain text: the styling of clap's styled help
+//! text is removed.
+//!
+//! Use [`help_model()`](crate::help_model) or
+//! [`help_model_command()`](crate::help_model_command) to build the model.
+//! If the `serde` feature of this crate is enabled, every type in the model
+//! implements `serde::Serialize`, and the model can be exported as JSON using
+//! `help_json()`.
+
+use crate::{
+    arg_details, arg_display_name, arg_group_rules, command_aliases,
+    command_arg_sections, command_usage, get_canonical_name, global_parents,
+    plain_text, subcommand_globals, GlobalArg,
+};
+
+/// The documentation of a command and its subcommands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct CommandDoc {
+    /// The name of the command.
+    pub name: String,
+    /// The names of the command and its parent commands, e.g.
+    /// `["tool", "remote", "add"]`.
+    pub path: Vec<String>,
+    /// The usage of the command, e.g. `tool remote add [OPTIONS] <NAME>`.
+    pub usage: String,
+    /// The short help text of the command.
+    pub about: Option<String>,
+    /// The long help text of the command.
+    pub long_about: Option<String>,
+    /// Help text shown before the arguments of the command.
+    pub before_help: Option<String>,
+    /// Help text shown after the arguments of the command.
+    pub after_help: Option<String>,
+    /// The visible aliases of the command.
+    pub aliases: Vec<String>,
+    /// The sections the arguments of the command are documented in, e.g.
+    /// "Arguments" and "Options".
+    pub arg_sections: Vec<ArgSectionDoc>,
+    /// The paths of the parent commands whose global arguments are also
+    /// accepted by this command.
+    pub global_args_from: Vec<Vec<String>>,
+    /// The argument groups of the command that constrain how their arguments
+    /// can be used.
+    pub arg_groups: Vec<ArgGroupDoc>,
+    /// The visible subcommands of the command.
+    pub subcommands: Vec<CommandDoc>,
+}
+
+/// A section of the arguments of a command, e.g. "Options".
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct ArgSectionDoc {
+    /// The heading of the section.
+    pub heading: String,
+    /// The arguments documented in the section, in order.
+    pub args: Vec<ArgDoc>,
+}
+
+/// The documentation of an argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct ArgDoc {
+    /// The id of the argument.
+    pub id: String,
+    /// The name the argument is referred to by, e.g. `--config` or `<FILE>`.
+    pub display_name: String,
+    /// The short flag of the argument, without the leading `-`.
+    pub short: Option<char>,
+    /// The long flag of the argument, without the leading `--`.
+    pub long: Option<String>,
+    /// The notation of the values taken by the argument. For positional
+    /// arguments this is the complete notation, e.g. `[FILE]`, and otherwise
+    /// the suffix shown after the flags of the argument, e.g. ` <FILE>`.
+    pub value_notation: String,
+    /// Whether the argument is positional.
+    pub positional: bool,
+    /// Whether the argument is required.
+    pub required: bool,
+    /// Whether the argument can be given more than once.
+    pub repeatable: bool,
+    /// Whether the argument is global, and so also accepted by every
+    /// subcommand.
+    pub global: bool,
+    /// Whether the argument cannot be used together with any other argument.
+    pub exclusive: bool,
+    /// The short help text of the argument.
+    pub help: Option<String>,
+    /// The long help text of the argument.
+    pub long_help: Option<String>,
+    /// The visible short and long aliases of the argument, e.g. `-C` or
+    /// `--colour`.
+    pub aliases: Vec<String>,
+    /// The display names of the arguments that cannot be used together with
+    /// this argument.
+    pub conflicts_with: Vec<String>,
+    /// The environment variable bound to the argument.
+    pub env: Option<String>,
+    /// The default values of the argument.
+    pub default_values: Vec<String>,
+    /// The visible possible values of the argument.
+    pub possible_values: Vec<PossibleValueDoc>,
+}
+
+/// The documentation of a possible value of an argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct PossibleValueDoc {
+    /// The value.
+    pub name: String,
+    /// The help text of the value.
+    pub help: Option<String>,
+}
+
+/// The documentation of an argument group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct ArgGroupDoc {
+    /// The id of the group.
+    pub id: String,
+    /// Whether one of the arguments of the group must be used.
+    pub required: bool,
+    /// Whether more than one of the arguments of the group can be used.
+    pub multiple: bool,
+    /// The display names of the visible arguments of the group.
+    pub args: Vec<String>,
+}
+
+//======================================
+// Building the model
+//======================================
+
+/// Build the documentation model of `command` and its visible subcommands.
+pub(crate) fn build_command_doc(
+    // Parent commands of `command`.
+    parent_command_path: &[String],
+    // Global arguments declared by the parent commands of `command`.
+    inherited_globals: &[GlobalArg],
+    command: &clap::Command,
+) -> CommandDoc {
+    // Append the name of `command` to `command_path`.
+    let command_path = {
+        let mut command_path = parent_command_path.to_vec();
+        command_path.push(get_canonical_name(command));
+        command_path
+    };
+
+    let arg_sections = command_arg_sections(command, inherited_globals)
+        .into_iter()
+        .map(|section| ArgSectionDoc {
+            heading: section.heading.to_owned(),
+            args: section
+                .args
+                .into_iter()
+                .map(|arg| build_arg_doc(command, arg))
+                .collect(),
+        })
+        .collect();
+
+    let arg_groups = arg_group_rules(command)
+        .into_iter()
+        .map(|group| ArgGroupDoc {
+            id: group.id.to_owned(),
+            required: group.required,
+            multiple: group.multiple,
+            args: group.members.into_iter().map(arg_display_name).collect(),
+        })
+        .collect();
+
+    let globals = subcommand_globals(inherited_globals, &command_path, command);
+
+    let subcommands = command
+        .get_subcommands()
+        // Don't document commands marked with `clap(hide = true)` (which
+        // includes `print-all-help`).
+        .filter(|subcommand| !subcommand.is_hide_set())
+        .map(|subcommand| {
+            build_command_doc(&command_path, &globals, subcommand)
+        })
+        .collect();
+
+    return CommandDoc {
+        name: get_canonical_name(command),
+        usage: command_usage(parent_command_path, command),
+        about: command.get_about().map(plain_text),
+        long_about: command.get_long_about().map(plain_text),
+        before_help: command
+            .get_before_long_help()
+            .or(command.get_before_help())
+            .map(plain_text),
+        after_help: command
+            .get_after_long_help()
+            .or(command.get_after_help())
+            .map(plain_text),
+        aliases: command_aliases(command),
+        arg_sections,
+        global_args_from: global_parents(inherited_globals)
+            .into_iter()
+            .map(<[String]>::to_vec)
+            .collect(),
+        arg_groups,
+        subcommands,
+        path: command_path,
+    };
+}
+
+fn build_arg_doc(command: &clap::Command, arg: &clap::Arg) -> ArgDoc {
+    let details = arg_details(command, arg);
+
+    return ArgDoc {
+        id: arg.get_id().to_string(),
+        display_name: arg_display_name(arg),
+        short: arg.get_short(),
+        long: arg.get_long().map(ToOwned::to_owned),
+        value_notation: details.value_notation,
+        positional: arg.is_positional(),
+        required: arg.is_required_set(),
+        repeatable: matches!(
+            arg.get_action(),
+            clap::ArgAction::Append | clap::ArgAction::Count
+        ),
+        global: arg.is_global_set(),
+        exclusive: details.exclusive,
+        help: arg.get_help().map(plain_text),
+        long_help: arg.get_long_help().map(plain_text),
+        aliases: details.aliases,
+        conflicts_with: details.conflicts,
+        env: details.env,
+        default_values: details.default_values,
+        possible_values: details
+            .possible_values
+            .into_iter()
+            .map(|pv| PossibleValueDoc {
+                name: pv.get_name().to_owned(),
+                help: pv.get_help().map(plain_text),
+            })
+            .collect(),
+    };
+}
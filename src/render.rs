@@ -0,0 +1,705 @@
+//! Customize how the pieces of the Markdown document are written.
+//!
+//! The Markdown document is written by calling the hooks of a [`Renderer`]
+//! while traversing the command tree. Every hook has a default implementation
+//! that writes the standard `clap-markdown` output, so a custom renderer only
+//! needs to override the hooks for the pieces it wants to change. Use
+//! [`MarkdownOptions::renderer()`] to set the renderer.
+
+use std::fmt::{self, Write};
+
+use clap::builder::PossibleValue;
+
+use crate::{
+    arg_details, code_list, command_usage, document_title, get_canonical_name,
+    indent, styled_markdown, AnchorStyle, ArgLayout, CommandLinks,
+    MarkdownFlavor, MarkdownOptions,
+};
+
+/// Hooks for writing each piece of the generated Markdown document.
+///
+/// The default implementation of each hook writes the standard output of this
+/// crate. Override individual hooks to change how that piece is written. For
+/// example, to show the name of each argument as a heading:
+///
+/// ```
+/// use std::fmt;
+///
+/// use clap_markdown::{CommandContext, MarkdownOptions, Renderer};
+///
+/// struct ArgHeadings;
+///
+/// impl Renderer for ArgHeadings {
+///     fn write_arg(
+///         &self,
+///         out: &mut dyn fmt::Write,
+///         _cx: &CommandContext,
+///         arg: &clap::Arg,
+///     ) -> fmt::Result {
+///         writeln!(out, "**{}**\n", arg.get_id())?;
+///
+///         if let Some(help) = arg.get_help() {
+///             writeln!(out, "{help}\n")?;
+///         }
+///
+///         Ok(())
+///     }
+/// }
+///
+/// let command = clap::Command::new("tool")
+///     .arg(clap::Arg::new("file").help("The file to read"));
+///
+/// let markdown = clap_markdown::help_markdown_command_custom(
+///     &command,
+///     &MarkdownOptions::new().renderer(ArgHeadings),
+/// );
+///
+/// assert!(markdown.contains("**file**\n\nThe file to read\n"));
+/// ```
+pub trait Renderer {
+    /// Write the title and introduction of the document.
+    fn write_title(
+        &self,
+        out: &mut dyn Write,
+        options: &MarkdownOptions,
+        command: &clap::Command,
+    ) -> fmt::Result {
+        writeln!(out, "# {}\n", document_title(options, command))?;
+
+        writeln!(
+            out,
+            "This document contains the help content for the `{}` command-line program.\n",
+            get_canonical_name(command)
+        )?;
+
+        Ok(())
+    }
+
+    /// Write the table of contents entry of a command.
+    fn write_table_of_contents_entry(
+        &self,
+        out: &mut dyn Write,
+        options: &MarkdownOptions,
+        entry: &TocEntry,
+    ) -> fmt::Result {
+        // Only links to a heading further down the same page get an arrow.
+        let arrow = if entry.is_same_page() { "↴" } else { "" };
+
+        if options.nested_table_of_contents {
+            // Indent by depth, and show only the name of this command; the
+            // parent commands are implied by the nesting of the list.
+            writeln!(
+                out,
+                "{}* [`{}`{arrow}]({})",
+                "  ".repeat(entry.depth()),
+                entry.command_path().last().unwrap(),
+                entry.href(),
+            )?;
+        } else {
+            writeln!(
+                out,
+                "* [`{}`{arrow}]({})",
+                entry.command_path().join(" "),
+                entry.href(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the heading of a command section, including its explicit
+    /// anchor, if any.
+    ///
+    /// Also used for the `{{heading}}` variable of
+    /// [`MarkdownOptions::command_template()`].
+    fn write_command_heading(
+        &self,
+        out: &mut dyn Write,
+        cx: &CommandContext,
+    ) -> fmt::Result {
+        if let Some(anchor) = cx.explicit_anchor() {
+            writeln!(out, "<a id=\"{anchor}\"></a>\n")?;
+        }
+
+        writeln!(
+            out,
+            "{} `{}`\n",
+            "#".repeat(cx.heading_level()),
+            cx.command_path().join(" "),
+        )?;
+
+        Ok(())
+    }
+
+    /// Write the heading of a command section, followed by the description
+    /// of the command.
+    ///
+    /// The default implementation calls [`Renderer::write_command_heading()`]
+    /// to write the heading.
+    fn write_command_header(
+        &self,
+        out: &mut dyn Write,
+        cx: &CommandContext,
+    ) -> fmt::Result {
+        let options = cx.options();
+        let command = cx.command();
+
+        self.write_command_heading(out, cx)?;
+
+        if let Some(long_about) = command.get_long_about() {
+            writeln!(
+                out,
+                "{}\n",
+                styled_markdown(options, command, long_about)
+            )?;
+        } else if let Some(about) = command.get_about() {
+            writeln!(out, "{}\n", styled_markdown(options, command, about))?;
+        }
+
+        if let Some(help) = command.get_before_long_help() {
+            writeln!(out, "{}\n", styled_markdown(options, command, help))?;
+        } else if let Some(help) = command.get_before_help() {
+            writeln!(out, "{}\n", styled_markdown(options, command, help))?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the usage line of a command.
+    fn write_usage(
+        &self,
+        out: &mut dyn Write,
+        cx: &CommandContext,
+    ) -> fmt::Result {
+        writeln!(
+            out,
+            "**Usage:** `{}`\n",
+            command_usage(cx.parent_command_path(), cx.command())
+        )?;
+
+        Ok(())
+    }
+
+    /// Write a section of the arguments of a command, e.g. "Options".
+    ///
+    /// The default implementation calls [`Renderer::write_arg()`] for each
+    /// argument, or [`Renderer::write_arg_table_row()`] if the
+    /// [`ArgLayout::Table`] layout is used.
+    fn write_arg_section(
+        &self,
+        out: &mut dyn Write,
+        cx: &CommandContext,
+        heading: &str,
+        args: &[&clap::Arg],
+    ) -> fmt::Result {
+        writeln!(out, "{}\n", cx.section_heading(heading))?;
+
+        let admonition = cx.options().arg_admonition.as_deref();
+
+        if let Some(kind) = admonition {
+            writeln!(out, ":::{kind}\n")?;
+        }
+
+        match cx.options().arg_layout {
+            ArgLayout::List => {
+                for arg in args {
+                    self.write_arg(out, cx, arg)?;
+                }
+            },
+            ArgLayout::Table => {
+                writeln!(
+                    out,
+                    "| Flag | Value | Default | Env | Description |"
+                )?;
+                writeln!(
+                    out,
+                    "|------|-------|---------|-----|-------------|"
+                )?;
+
+                for arg in args {
+                    self.write_arg_table_row(out, cx, arg)?;
+                }
+            },
+        }
+
+        writeln!(out)?;
+
+        if admonition.is_some() {
+            writeln!(out, ":::\n")?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the documentation of an argument.
+    ///
+    /// The default implementation calls [`Renderer::write_possible_values()`]
+    /// if the argument has any visible possible values.
+    fn write_arg(
+        &self,
+        out: &mut dyn Write,
+        cx: &CommandContext,
+        arg: &clap::Arg,
+    ) -> fmt::Result {
+        let options = cx.options();
+        let command = cx.command();
+        let details = arg_details(command, arg);
+
+        // Markdown list item
+        write!(out, "* {}", code_list(&details.names()))?;
+
+        //--------------------
+        // Arg requirements
+        //--------------------
+
+        if !details.notes.is_empty() {
+            write!(out, " ({})", details.notes.join(", "))?;
+        }
+
+        if let Some(help) = details.help {
+            out.write_str(&indent(
+                &styled_markdown(options, command, help),
+                " — ",
+                "   ",
+            ))?
+        } else {
+            writeln!(out)?;
+        }
+
+        //--------------------
+        // Arg aliases
+        //--------------------
+
+        let aliases = &details.aliases;
+
+        if options.show_aliases && !aliases.is_empty() {
+            if aliases.len() > 1 {
+                // Plural
+                writeln!(out, "\n  Aliases: {}", code_list(aliases))?;
+            } else {
+                // Singular
+                writeln!(out, "\n  Alias: {}", code_list(aliases))?;
+            }
+        }
+
+        //--------------------
+        // Arg conflicts
+        //--------------------
+
+        // NOTE: `requires`, `required_unless_present` and `required_if_eq`
+        //       rules are not documented, because clap does not provide
+        //       public getters for them. See the "Argument relationships"
+        //       section of the crate documentation.
+
+        if details.exclusive {
+            writeln!(out, "\n  Cannot be used with any other arguments.")?;
+        } else if !details.conflicts.is_empty() {
+            writeln!(
+                out,
+                "\n  Cannot be used with {}.",
+                code_list(&details.conflicts)
+            )?;
+        }
+
+        //--------------------
+        // Arg environment variable
+        //--------------------
+
+        if let Some(env) = &details.env {
+            writeln!(out, "\n  Environment variable: `{env}`")?;
+        }
+
+        //--------------------
+        // Arg default values
+        //--------------------
+
+        let default_values = &details.default_values;
+
+        if !default_values.is_empty() {
+            if default_values.len() > 1 {
+                // Plural
+                writeln!(
+                    out,
+                    "\n  Default values: {}",
+                    code_list(default_values)
+                )?;
+            } else {
+                // Singular
+                writeln!(
+                    out,
+                    "\n  Default value: {}",
+                    code_list(default_values)
+                )?;
+            }
+        }
+
+        //--------------------
+        // Arg possible values
+        //--------------------
+
+        if !details.possible_values.is_empty() {
+            self.write_possible_values(out, cx, arg, &details.possible_values)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the documentation of an argument as a row of a Markdown table,
+    /// when the [`ArgLayout::Table`] layout is used.
+    ///
+    /// The row has a cell for each of the flags, value, default value,
+    /// environment variable, and description of the argument.
+    fn write_arg_table_row(
+        &self,
+        out: &mut dyn Write,
+        cx: &CommandContext,
+        arg: &clap::Arg,
+    ) -> fmt::Result {
+        let options = cx.options();
+        let command = cx.command();
+        let details = arg_details(command, arg);
+
+        //--------------------
+        // Flag and value
+        //--------------------
+
+        let (mut flag, value) = if details.flags.is_empty() {
+            // The value notation of a positional argument is its name.
+            (code_list(&details.names()), "")
+        } else {
+            (
+                code_list(&details.flags),
+                details.value_notation.trim_start(),
+            )
+        };
+
+        if !details.notes.is_empty() {
+            flag.push_str(&format!(" ({})", details.notes.join(", ")));
+        }
+
+        let value = match value {
+            "" => String::new(),
+            value => format!("`{value}`"),
+        };
+
+        //--------------------
+        // Env
+        //--------------------
+
+        let env = match &details.env {
+            Some(env) => format!("`{env}`"),
+            None => String::new(),
+        };
+
+        //--------------------
+        // Description
+        //--------------------
+
+        // Paragraphs of the description cell.
+        let mut description: Vec<String> = Vec::new();
+
+        if let Some(help) = details.help {
+            description.push(styled_markdown(options, command, help));
+        }
+
+        let aliases = &details.aliases;
+
+        if options.show_aliases && !aliases.is_empty() {
+            let label = if aliases.len() > 1 {
+                "Aliases"
+            } else {
+                "Alias"
+            };
+
+            description.push(format!("{label}: {}", code_list(aliases)));
+        }
+
+        if details.exclusive {
+            description.push("Cannot be used with any other arguments.".into());
+        } else if !details.conflicts.is_empty() {
+            description.push(format!(
+                "Cannot be used with {}.",
+                code_list(&details.conflicts)
+            ));
+        }
+
+        let possible_values = &details.possible_values;
+
+        if possible_values.iter().any(|pv| pv.get_help().is_some()) {
+            let mut text = String::from("Possible values:");
+
+            for pv in possible_values {
+                match pv.get_help() {
+                    Some(help) => text.push_str(&format!(
+                        "\n- `{}`: {}",
+                        pv.get_name(),
+                        styled_markdown(options, command, help)
+                    )),
+                    None => text.push_str(&format!("\n- `{}`", pv.get_name())),
+                }
+            }
+
+            description.push(text);
+        } else if !possible_values.is_empty() {
+            let names: Vec<String> = possible_values
+                .iter()
+                .map(|pv| pv.get_name().to_owned())
+                .collect();
+
+            description.push(format!("Possible values: {}", code_list(&names)));
+        }
+
+        // MDX only supports self-closing void elements.
+        let line_break = match options.flavor {
+            MarkdownFlavor::Standard => "<br>",
+            MarkdownFlavor::Mdx => "<br/>",
+        };
+
+        writeln!(
+            out,
+            "| {} | {} | {} | {} | {} |",
+            table_cell(&flag, line_break),
+            table_cell(&value, line_break),
+            table_cell(&code_list(&details.default_values), line_break),
+            table_cell(&env, line_break),
+            table_cell(&description.join("\n\n"), line_break),
+        )?;
+
+        Ok(())
+    }
+
+    /// Write the visible possible values of an argument.
+    ///
+    /// Only called if `possible_values` is not empty.
+    fn write_possible_values(
+        &self,
+        out: &mut dyn Write,
+        cx: &CommandContext,
+        _arg: &clap::Arg,
+        possible_values: &[PossibleValue],
+    ) -> fmt::Result {
+        let any_have_help: bool =
+            possible_values.iter().any(|pv| pv.get_help().is_some());
+
+        if any_have_help {
+            // If any of the possible values have help text, print them
+            // as a separate item in a bulleted list, and include the
+            // help text for those that have it. E.g.:
+            //
+            //     Possible values:
+            //     - `value1`:
+            //       The help text
+            //     - `value2`
+            //     - `value3`:
+            //       The help text
+
+            let text: String = possible_values
+                .iter()
+                .map(|pv| match pv.get_help() {
+                    Some(help) => format!(
+                        "  - `{}`:\n    {}\n",
+                        pv.get_name(),
+                        styled_markdown(cx.options(), cx.command(), help)
+                    ),
+                    None => format!("  - `{}`\n", pv.get_name()),
+                })
+                .collect::<Vec<String>>()
+                .join("");
+
+            writeln!(out, "\n  Possible values:\n{text}")?;
+        } else {
+            // If none of the possible values have any documentation, print
+            // them all inline on a single line.
+            let text: String = possible_values
+                .iter()
+                // Note: PossibleValue::get_name_and_aliases() is not used
+                //       here, because clap only supports hidden possible value
+                //       aliases, and hidden aliases are not documented.
+                .map(|pv| format!("`{}`", pv.get_name()))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            writeln!(out, "\n  Possible values: {text}\n")?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the footer of the document.
+    ///
+    /// Only called if [`MarkdownOptions::show_footer()`] is enabled.
+    fn write_footer(
+        &self,
+        out: &mut dyn Write,
+        options: &MarkdownOptions,
+        _command: &clap::Command,
+    ) -> fmt::Result {
+        if options.flavor == MarkdownFlavor::Mdx {
+            // Keep the JSX on one line, so its text isn't parsed as Markdown.
+            write!(
+                out,
+                r#"<hr/>
+
+<small><i>This document was generated automatically by <a href="https://crates.io/crates/clap-markdown"><code>clap-markdown</code></a>.</i></small>
+"#
+            )?;
+
+            return Ok(());
+        }
+
+        write!(
+            out,
+            r#"<hr/>
+
+<small><i>
+    This document was generated automatically by
+    <a href="https://crates.io/crates/clap-markdown"><code>clap-markdown</code></a>.
+</i></small>
+"#
+        )?;
+
+        Ok(())
+    }
+}
+
+/// The default [`Renderer`], which writes the standard output of this crate.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarkdownRenderer;
+
+impl Renderer for MarkdownRenderer {}
+
+//======================================
+// Hook contexts
+//======================================
+
+/// The command section being written, passed to the [`Renderer`] hooks.
+pub struct CommandContext<'a> {
+    pub(crate) options: &'a MarkdownOptions,
+    pub(crate) links: CommandLinks<'a>,
+    pub(crate) parent_command_path: &'a [String],
+    pub(crate) command_path: &'a [String],
+    pub(crate) command: &'a clap::Command,
+    pub(crate) heading_level: usize,
+}
+
+impl<'a> CommandContext<'a> {
+    /// The options the document is being generated with.
+    pub fn options(&self) -> &'a MarkdownOptions {
+        return self.options;
+    }
+
+    /// The command being documented.
+    pub fn command(&self) -> &'a clap::Command {
+        return self.command;
+    }
+
+    /// The names of the parent commands of the command, e.g.
+    /// `["tool", "remote"]` for `tool remote add`.
+    pub fn parent_command_path(&self) -> &'a [String] {
+        return self.parent_command_path;
+    }
+
+    /// The names of the command and its parent commands, e.g.
+    /// `["tool", "remote", "add"]`.
+    pub fn command_path(&self) -> &'a [String] {
+        return self.command_path;
+    }
+
+    /// The heading level of the command section, from `1` to `6`.
+    pub fn heading_level(&self) -> usize {
+        return self.heading_level;
+    }
+
+    /// Get the link target for the documentation of the command at
+    /// `command_path`, e.g. `#tool-remote` or `../remote.md`.
+    pub fn href(&self, command_path: &[String]) -> String {
+        return self.links.href(command_path);
+    }
+
+    /// Get the heading of a subsection of the command section, e.g.
+    /// `###### **Options:**` for the "Options" subsection.
+    ///
+    /// See [`MarkdownOptions::section_heading_template()`].
+    pub fn section_heading(&self, heading: &str) -> String {
+        return self.options.section_heading(heading);
+    }
+
+    /// Get the id that must be set explicitly on the heading of the command
+    /// section, if [`AnchorStyle::Explicit`] is used.
+    pub fn explicit_anchor(&self) -> Option<&'a str> {
+        match (self.links, self.options.anchor_style) {
+            (CommandLinks::Anchor(anchors), AnchorStyle::Explicit) => {
+                Some(anchors[self.command_path].as_str())
+            },
+            _ => None,
+        }
+    }
+}
+
+/// An entry of the table of contents, passed to
+/// [`Renderer::write_table_of_contents_entry()`].
+pub struct TocEntry<'a> {
+    pub(crate) command: &'a clap::Command,
+    pub(crate) command_path: &'a [String],
+    pub(crate) depth: usize,
+    pub(crate) href: String,
+    pub(crate) is_same_page: bool,
+}
+
+impl<'a> TocEntry<'a> {
+    /// The command the entry refers to.
+    pub fn command(&self) -> &'a clap::Command {
+        return self.command;
+    }
+
+    /// The names of the command and its parent commands, e.g.
+    /// `["tool", "remote", "add"]`.
+    pub fn command_path(&self) -> &'a [String] {
+        return self.command_path;
+    }
+
+    /// The subcommand nesting depth of the command, `0` for the root command.
+    pub fn depth(&self) -> usize {
+        return self.depth;
+    }
+
+    /// The link target for the documentation of the command.
+    pub fn href(&self) -> &str {
+        return &self.href;
+    }
+
+    /// Whether the command is documented further down the same page as the
+    /// table of contents.
+    pub fn is_same_page(&self) -> bool {
+        return self.is_same_page;
+    }
+}
+
+//======================================
+// Helpers
+//======================================
+
+/// Format `text` as the content of a Markdown table cell.
+///
+/// Table cells can't span multiple lines, so line breaks are replaced with
+/// `line_break` elements. Pipes are escaped so they don't end the cell, which
+/// GFM also supports inside code spans. Pipes that are already escaped, e.g.
+/// by [`HelpTextFormat::PlainText`](crate::HelpTextFormat::PlainText), are
+/// left unchanged.
+fn table_cell(text: &str, line_break: &str) -> String {
+    let mut cell = String::with_capacity(text.len());
+    // The number of backslashes immediately before the current character.
+    let mut backslashes = 0;
+
+    for c in text.trim().chars() {
+        match c {
+            '|' if backslashes % 2 == 0 => cell.push_str("\\|"),
+            '\n' => cell.push_str(line_break),
+            _ => cell.push(c),
+        }
+
+        backslashes = if c == '\\' { backslashes + 1 } else { 0 };
+    }
+
+    return cell;
+}
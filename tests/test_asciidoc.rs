@@ -0,0 +1,211 @@
+use clap::{Arg, ArgAction, Command};
+use clap_markdown::{help_asciidoc_command_custom, AsciiDocOptions};
+
+use pretty_assertions::assert_eq;
+
+/// Test the AsciiDoc document of a command with subcommands and globals.
+#[test]
+fn test_asciidoc() {
+    let app = Command::new("tool")
+        .about("A tool.")
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Print more output"),
+        )
+        .subcommand(
+            Command::new("remote")
+                .about("Manage remotes.")
+                .visible_alias("r")
+                .arg(Arg::new("name").required(true).help("Remote name"))
+                .arg(
+                    Arg::new("mode")
+                        .long("mode")
+                        .value_parser(["fetch", "push"])
+                        .default_value("fetch"),
+                ),
+        )
+        .disable_help_flag(true)
+        .disable_help_subcommand(true);
+
+    assert_eq!(
+        help_asciidoc_command_custom(
+            &app,
+            &AsciiDocOptions::new().show_footer(false)
+        ),
+        r##"= Command-Line Help for `+tool+`
+
+This document contains the help content for the `+tool+` command-line program.
+
+*Command Overview:*
+
+* <<tool,`+tool+`>>
+* <<tool-remote,`+tool remote+`>>
+
+[#tool]
+== `+tool+`
+
+A tool.
+
+.Usage
+[source,shell]
+----
+tool [OPTIONS] [COMMAND]
+----
+
+.Subcommands
+<<tool-remote,`+remote+`>> (alias: `+r+`):: Manage remotes.
+
+[#tool-global-options]
+.Global Options
+`+-v+`, `+--verbose+`::
+Print more output
+
+[#tool-remote]
+=== `+tool remote+`
+
+Manage remotes.
+
+.Usage
+[source,shell]
+----
+tool remote [OPTIONS] <name>
+----
+
+*Command Alias:* `+r+`
+
+.Arguments
+`+<NAME>+` (required)::
+Remote name
+
+.Options
+`+--mode <MODE>+`::
+Default value: `+fetch+`
++
+Possible values: `+fetch+`, `+push+`
+
+This command also accepts the <<tool-global-options,global options>> of `+tool+`.
+
+"##
+    );
+}
+
+/// Test that section levels follow nesting, up to AsciiDoc's deepest level.
+#[test]
+fn test_asciidoc_section_levels() {
+    let app =
+        Command::new("a").subcommand(Command::new("b").subcommand(
+            Command::new("c").subcommand(Command::new("d").subcommand(
+                Command::new("e").subcommand(
+                    Command::new("f").subcommand(Command::new("g")),
+                ),
+            )),
+        ));
+
+    let asciidoc = help_asciidoc_command_custom(
+        &app,
+        &AsciiDocOptions::new()
+            .title("Custom Title".to_owned())
+            .show_table_of_contents(false),
+    );
+
+    assert!(asciidoc.starts_with("= Custom Title\n\n"));
+    assert!(!asciidoc.contains("*Command Overview:*"));
+
+    // AsciiDoc has no section level deeper than 5.
+    for (heading, expected) in [
+        ("`+a+`", "=="),
+        ("`+a b+`", "==="),
+        ("`+a b c+`", "===="),
+        ("`+a b c d+`", "====="),
+        ("`+a b c d e+`", "======"),
+        ("`+a b c d e f+`", "======"),
+        ("`+a b c d e f g+`", "======"),
+    ] {
+        assert!(
+            asciidoc.contains(&format!("\n{expected} {heading}\n")),
+            "missing heading {expected} {heading}"
+        );
+    }
+}
+
+/// Test that only the visible command and argument aliases are documented.
+#[test]
+fn test_asciidoc_aliases() {
+    let app = Command::new("tool")
+        .subcommand(
+            Command::new("remove")
+                .about("Remove things.")
+                .visible_aliases(["rm", "delete"])
+                .alias("hidden")
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .visible_short_alias('F')
+                        .visible_alias("yes")
+                        .action(ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(Command::new("list").visible_alias("ls"))
+        .disable_help_flag(true)
+        .disable_help_subcommand(true);
+
+    let asciidoc = help_asciidoc_command_custom(
+        &app,
+        &AsciiDocOptions::new()
+            .show_footer(false)
+            .show_table_of_contents(false),
+    );
+
+    assert!(asciidoc.contains(
+        "<<tool-remove,`+remove+`>> (aliases: `+rm+`, `+delete+`):: \
+         Remove things.\n\
+         <<tool-list,`+list+`>> (alias: `+ls+`)::\n"
+    ));
+    assert!(asciidoc.contains("*Command Aliases:* `+rm+`, `+delete+`\n"));
+    assert!(asciidoc.contains("*Command Alias:* `+ls+`\n"));
+    assert!(asciidoc.contains("`+--force+`::\nAliases: `+-F+`, `+--yes+`\n"));
+    assert!(!asciidoc.contains("hidden"));
+}
+
+/// Test that help text isn't interpreted as AsciiDoc markup.
+#[test]
+fn test_asciidoc_escaping() {
+    let app = Command::new("tool")
+        .about("Matches *.rs and *.toml files, see <<other>> and {version}.")
+        .arg(
+            Arg::new("pattern")
+                .long("pattern")
+                .help_heading("Options [advanced]")
+                .help("A glob like my_file_*.rs, or a+b.\n\n* Not a list"),
+        )
+        .disable_help_flag(true);
+
+    let asciidoc = help_asciidoc_command_custom(
+        &app,
+        &AsciiDocOptions::new()
+            .show_footer(false)
+            .show_table_of_contents(false),
+    );
+
+    assert!(asciidoc.contains(
+        "\npass:c[Matches *.rs and *.toml files, see <<other>> and \
+         {version}.]\n"
+    ));
+    assert!(asciidoc.contains("\n.pass:c[Options [advanced\\]]\n"));
+    assert!(asciidoc.contains(
+        "`+--pattern <PATTERN>+`::\n\
+         pass:c[A glob like my_file_*.rs, or a+b.]\n\
+         +\n\
+         pass:c[* Not a list]\n"
+    ));
+
+    // Text without markup characters is left unchanged.
+    let app = Command::new("tool").about("Plain text, e.g. <FILE> & more.");
+
+    assert!(help_asciidoc_command_custom(&app, &AsciiDocOptions::new())
+        .contains("\nPlain text, e.g. <FILE> & more.\n"));
+}
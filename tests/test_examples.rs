@@ -24,4 +24,16 @@ fn test_example_complex_app() {
         include_str!("../docs/examples/complex-app-custom.md"),
         "Mismatch testing CUSTOM Markdown output"
     );
+
+    assert_eq!(
+        clap_markdown::help_html::<complex_app::Cli>(),
+        include_str!("../docs/examples/complex-app.html"),
+        "Mismatch testing HTML output"
+    );
+
+    assert_eq!(
+        clap_markdown::help_asciidoc::<complex_app::Cli>(),
+        include_str!("../docs/examples/complex-app.adoc"),
+        "Mismatch testing AsciiDoc output"
+    );
 }
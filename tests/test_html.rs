@@ -0,0 +1,122 @@
+use clap::{Arg, ArgAction, Command};
+use clap_markdown::{help_html_command_custom, HtmlOptions};
+
+use pretty_assertions::assert_eq;
+
+/// Test the HTML document of a command with subcommands and globals.
+#[test]
+fn test_html() {
+    let app = Command::new("tool")
+        .about("A tool for <things> & stuff.")
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Print more output"),
+        )
+        .subcommand(
+            Command::new("remote")
+                .about("Manage remotes.")
+                .visible_alias("r")
+                .arg(Arg::new("name").required(true).help("Remote name"))
+                .arg(
+                    Arg::new("mode")
+                        .long("mode")
+                        .value_parser(["fetch", "push"])
+                        .default_value("fetch"),
+                ),
+        )
+        .disable_help_flag(true)
+        .disable_help_subcommand(true);
+
+    assert_eq!(
+        help_html_command_custom(
+            &app,
+            &HtmlOptions::new()
+                .show_footer(false)
+                .embed_stylesheet(false)
+        ),
+        r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Command-Line Help for tool</title>
+</head>
+<body>
+<h1>Command-Line Help for <code>tool</code></h1>
+<p>This document contains the help content for the <code>tool</code> command-line program.</p>
+<nav>
+<p><strong>Command Overview:</strong></p>
+<ul>
+<li><a href="#tool"><code>tool</code></a></li>
+<li><a href="#tool-remote"><code>tool remote</code></a></li>
+</ul>
+</nav>
+<section>
+<h2 id="tool"><code>tool</code></h2>
+<p>A tool for &lt;things&gt; &amp; stuff.</p>
+<p><strong>Usage:</strong> <code>tool [OPTIONS] [COMMAND]</code></p>
+<h3>Subcommands:</h3>
+<dl>
+<dt><a href="#tool-remote"><code>remote</code></a> (alias: <code>r</code>)</dt>
+<dd>Manage remotes.</dd>
+</dl>
+<h3 id="tool-global-options">Global Options:</h3>
+<dl>
+<dt><code>-v</code>, <code>--verbose</code></dt>
+<dd>
+<p>Print more output</p>
+</dd>
+</dl>
+</section>
+<section>
+<h2 id="tool-remote"><code>tool remote</code></h2>
+<p>Manage remotes.</p>
+<p><strong>Usage:</strong> <code>tool remote [OPTIONS] &lt;name&gt;</code></p>
+<p><strong>Command Alias:</strong> <code>r</code></p>
+<h3>Arguments:</h3>
+<dl>
+<dt><code>&lt;NAME&gt;</code> (required)</dt>
+<dd>
+<p>Remote name</p>
+</dd>
+</dl>
+<h3>Options:</h3>
+<dl>
+<dt><code>--mode &lt;MODE&gt;</code></dt>
+<dd>
+<p>Default value: <code>fetch</code></p>
+<p>Possible values:</p>
+<ul>
+<li><code>fetch</code></li>
+<li><code>push</code></li>
+</ul>
+</dd>
+</dl>
+<p>This command also accepts the <a href="#tool-global-options">global options</a> of <code>tool</code>.</p>
+</section>
+</body>
+</html>
+"##
+    );
+}
+
+/// Test embedding the default or a custom stylesheet in the HTML document.
+#[test]
+fn test_html_stylesheet() {
+    let app = Command::new("tool");
+
+    let html = help_html_command_custom(&app, &HtmlOptions::new());
+    assert!(html.contains(&format!(
+        "<style>\n{}\n</style>\n",
+        clap_markdown::DEFAULT_HTML_STYLESHEET.trim_end()
+    )));
+
+    let html = help_html_command_custom(
+        &app,
+        &HtmlOptions::new().stylesheet("body { color: red; }\n".to_owned()),
+    );
+    assert!(html.contains("<style>\nbody { color: red; }\n</style>\n"));
+}
@@ -0,0 +1,187 @@
+use clap::{Arg, ArgAction, Command};
+use clap_markdown::{help_man_command_pages_custom, ManOptions};
+
+use pretty_assertions::assert_eq;
+
+/// Test the man page generated for each command.
+#[test]
+fn test_man_pages() {
+    let app = Command::new("tool")
+        .version("1.2.3")
+        .about("A tool.\n\nIt does things.")
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Print more output"),
+        )
+        .subcommand(
+            Command::new("remote")
+                .about("Manage remotes.")
+                .arg(
+                    Arg::new("name").required(true).help(".name of the remote"),
+                )
+                .arg(
+                    Arg::new("mode")
+                        .long("mode")
+                        .value_parser(["fetch", "push"])
+                        .default_value("fetch"),
+                ),
+        )
+        .subcommand(Command::new("secret").hide(true))
+        .disable_help_flag(true)
+        .disable_version_flag(true)
+        .disable_help_subcommand(true);
+
+    let pages = help_man_command_pages_custom(
+        &app,
+        &ManOptions::new()
+            .date("2024-06-15".to_owned())
+            .manual("Tool Manual".to_owned()),
+    );
+
+    assert_eq!(
+        pages.keys().collect::<Vec<_>>(),
+        ["tool-remote.1", "tool.1"]
+    );
+
+    assert_eq!(
+        pages["tool.1"],
+        r#".TH "TOOL" "1" "2024\-06\-15" "tool 1.2.3" "Tool Manual"
+.SH NAME
+tool \- A tool.
+.SH SYNOPSIS
+\fBtool\fR [OPTIONS] [COMMAND]
+.SH DESCRIPTION
+A tool.
+.PP
+It does things.
+.SH COMMANDS
+.TP
+\fBtool\-remote\fR(1)
+Manage remotes.
+.SH OPTIONS
+.SS "Global Options"
+.TP
+\fB\-v\fR, \fB\-\-verbose\fR
+Print more output
+.SH "SEE ALSO"
+\fBtool\-remote\fR(1)
+"#
+    );
+
+    assert_eq!(
+        pages["tool-remote.1"],
+        r#".TH "TOOL\-REMOTE" "1" "2024\-06\-15" "tool 1.2.3" "Tool Manual"
+.SH NAME
+tool\-remote \- Manage remotes.
+.SH SYNOPSIS
+\fBtool remote\fR [OPTIONS] <name>
+.SH DESCRIPTION
+Manage remotes.
+.SH OPTIONS
+.SS "Arguments"
+.TP
+<NAME> (required)
+\&.name of the remote
+.SS "Options"
+.TP
+\fB\-\-mode\fR <MODE>
+Default value: \fBfetch\fR
+.IP
+Possible values:
+.RS
+.IP \(bu 2
+\fBfetch\fR
+.IP \(bu 2
+\fBpush\fR
+.RE
+.PP
+This command also accepts the global options of \fBtool\fR(1).
+.SH "SEE ALSO"
+\fBtool\fR(1)
+"#
+    );
+}
+
+/// Test that help text lines starting with `.` or `'` are escaped, so that
+/// they aren't interpreted as roff requests.
+#[test]
+fn test_man_escaping() {
+    use clap::builder::PossibleValue;
+
+    let app = Command::new("tool")
+        .long_about(
+            ".leading dot\n'leading quote\n  .indented dot\n\n\
+             back\\slash and -dash",
+        )
+        .after_help("'after help")
+        .arg(
+            Arg::new("mode")
+                .long("mode")
+                .help("'quoted help")
+                .value_parser([PossibleValue::new("a").help(".dot value")]),
+        )
+        .disable_help_flag(true)
+        .disable_version_flag(true);
+
+    let pages = help_man_command_pages_custom(
+        &app,
+        &ManOptions::new()
+            .date("2024-06-15".to_owned())
+            .manual("The \"Tool\" Manual".to_owned()),
+    );
+
+    let page = &pages["tool.1"];
+
+    assert!(page.starts_with(
+        ".TH \"TOOL\" \"1\" \"2024\\-06\\-15\" \"tool\" \"The \\(dqTool\\(dq Manual\"\n"
+    ));
+    assert!(page.contains(
+        "\
+.SH DESCRIPTION
+\\&.leading dot
+\\&'leading quote
+\\&.indented dot
+.PP
+back\\eslash and \\-dash
+.PP
+\\&'after help
+"
+    ));
+    assert!(page.contains("\\fB\\-\\-mode\\fR <MODE>\n\\&'quoted help\n"));
+    assert!(page.contains("\\fBa\\fR: \\&.dot value\n"));
+
+    // No line other than a request starts with a control character.
+    for line in page.lines() {
+        if line.starts_with(['.', '\'']) {
+            assert!(
+                line.starts_with(".TH ")
+                    || line.starts_with(".SH ")
+                    || matches!(line, ".PP" | ".TP" | ".IP" | ".RS" | ".RE")
+                    || line.starts_with(".IP "),
+                "unescaped line: {line}"
+            );
+        }
+    }
+}
+
+/// Test that the NAME section summary is plain text, without the ANSI escape
+/// sequences of styled help text.
+#[test]
+fn test_man_styled_name() {
+    use clap::builder::{styling::Style, StyledStr};
+
+    let bold = Style::new().bold();
+
+    let app = Command::new("tool")
+        .about(StyledStr::from(format!("Use {bold}bold{bold:#} text")))
+        .disable_help_flag(true);
+
+    let pages = help_man_command_pages_custom(&app, &ManOptions::new());
+
+    assert!(pages["tool.1"].contains("\n.SH NAME\ntool \\- Use bold text\n"));
+    assert!(!pages["tool.1"].contains('\x1b'));
+}
@@ -154,3 +154,1920 @@ This program does things.
 "
     );
 }
+
+/// Test that streaming Markdown to an `io::Write` sink produces the same
+/// output as building a `String`, and that I/O errors are propagated.
+#[test]
+fn test_write_help_markdown_to() {
+    use std::io;
+
+    let app = Command::new("my-program-name")
+        .about("This program does things.")
+        .subcommand(Command::new("sub").about("A subcommand."))
+        .arg(Arg::new("foo").short('f'));
+
+    let options = MarkdownOptions::new();
+
+    let mut bytes: Vec<u8> = Vec::new();
+    clap_markdown::write_help_markdown_to(&mut bytes, &app, &options).unwrap();
+
+    assert_eq!(
+        String::from_utf8(bytes).unwrap(),
+        help_markdown_command_custom(&app, &options)
+    );
+
+    let mut string = String::new();
+    clap_markdown::write_help_markdown_fmt(&mut string, &app, &options)
+        .unwrap();
+
+    assert_eq!(string, help_markdown_command_custom(&app, &options));
+
+    //------------------------------------------
+    // Test that writer errors are not swallowed
+    //------------------------------------------
+
+    struct FailingWriter;
+
+    impl io::Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let error = clap_markdown::write_help_markdown_to(
+        &mut FailingWriter,
+        &app,
+        &options,
+    )
+    .unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+    assert_eq!(error.to_string(), "pipe closed");
+}
+
+/// Test that `MarkdownOptions::nested_headings()` gives each command section a
+/// heading level matching its subcommand nesting depth.
+#[test]
+fn test_nested_headings() {
+    let app = Command::new("tool").subcommand(
+        Command::new("remote")
+            .subcommand(Command::new("add"))
+            .subcommand(Command::new("remove")),
+    );
+
+    fn command_headings(markdown: &str) -> Vec<&str> {
+        markdown
+            .lines()
+            .filter(|line| line.starts_with("#") && line.contains(" `"))
+            .filter(|line| !line.starts_with("# Command-Line Help"))
+            .collect()
+    }
+
+    //-------------------------------
+    // Default: all headings are flat
+    //-------------------------------
+
+    assert_eq!(
+        command_headings(&help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+        )),
+        [
+            "## `tool`",
+            "## `tool remote`",
+            "## `tool remote add`",
+            "## `tool remote remove`",
+        ]
+    );
+
+    //-----------------
+    // Nested headings
+    //-----------------
+
+    assert_eq!(
+        command_headings(&help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new().nested_headings(true)
+        )),
+        [
+            "## `tool`",
+            "### `tool remote`",
+            "#### `tool remote add`",
+            "#### `tool remote remove`",
+        ]
+    );
+
+    //-----------------------------------------------------
+    // Custom base level, with nesting deeper than `######`
+    //-----------------------------------------------------
+
+    let deep_app =
+        Command::new("a").subcommand(Command::new("b").subcommand(
+            Command::new("c").subcommand(Command::new("d").subcommand(
+                Command::new("e").subcommand(
+                    Command::new("f").subcommand(Command::new("g")),
+                ),
+            )),
+        ));
+
+    assert_eq!(
+        command_headings(&help_markdown_command_custom(
+            &deep_app,
+            &MarkdownOptions::new()
+                .nested_headings(true)
+                .heading_base_level(3)
+        )),
+        [
+            "### `a`",
+            "#### `a b`",
+            "##### `a b c`",
+            "###### `a b c d`",
+            "###### `a b c d e`",
+            "###### `a b c d e f`",
+            "###### `a b c d e f g`",
+        ]
+    );
+}
+
+/// Test the nested table of contents, and limiting the table of contents
+/// depth.
+#[test]
+fn test_nested_table_of_contents() {
+    let app = Command::new("tool")
+        .subcommand(
+            Command::new("remote")
+                .subcommand(Command::new("add"))
+                .subcommand(Command::new("remove").hide(true)),
+        )
+        .subcommand(Command::new("status"));
+
+    fn table_of_contents(markdown: &str) -> Vec<&str> {
+        markdown
+            .lines()
+            .skip_while(|line| *line != "**Command Overview:**")
+            .skip(2)
+            .take_while(|line| !line.is_empty())
+            .collect()
+    }
+
+    assert_eq!(
+        table_of_contents(&help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new().nested_table_of_contents(true)
+        )),
+        [
+            "* [`tool`↴](#tool)",
+            "  * [`remote`↴](#tool-remote)",
+            "    * [`add`↴](#tool-remote-add)",
+            "  * [`status`↴](#tool-status)",
+        ]
+    );
+
+    assert_eq!(
+        table_of_contents(&help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+                .nested_table_of_contents(true)
+                .table_of_contents_max_depth(1)
+        )),
+        [
+            "* [`tool`↴](#tool)",
+            "  * [`remote`↴](#tool-remote)",
+            "  * [`status`↴](#tool-status)",
+        ]
+    );
+
+    assert_eq!(
+        table_of_contents(&help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new().table_of_contents_max_depth(0)
+        )),
+        ["* [`tool`↴](#tool)"]
+    );
+
+    // Limiting the table of contents depth does not affect the body.
+    assert!(help_markdown_command_custom(
+        &app,
+        &MarkdownOptions::new().table_of_contents_max_depth(0)
+    )
+    .contains("## `tool remote add`"));
+}
+
+/// Test splitting the generated Markdown into a separate page per command.
+#[test]
+fn test_markdown_pages() {
+    let app = Command::new("tool")
+        .about("A tool.")
+        .subcommand(
+            Command::new("remote")
+                .about("Manage remotes.")
+                .subcommand(
+                    Command::new("add")
+                        .about("Add a remote.")
+                        .arg(Arg::new("name").required(true)),
+                )
+                .subcommand(Command::new("secret").hide(true)),
+        )
+        .subcommand(Command::new("status").about("Show status."));
+
+    let pages = clap_markdown::help_markdown_command_pages(
+        &app,
+        &MarkdownOptions::new().show_footer(false),
+    );
+
+    assert_eq!(
+        pages.keys().collect::<Vec<_>>(),
+        [
+            "index.md",
+            "tool/remote.md",
+            "tool/remote/add.md",
+            "tool/status.md"
+        ]
+    );
+
+    assert_eq!(
+        pages["index.md"],
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+**Command Overview:**
+
+* [`tool`](index.md)
+* [`tool remote`](tool/remote.md)
+* [`tool remote add`](tool/remote/add.md)
+* [`tool status`](tool/status.md)
+
+## `tool`
+
+A tool.
+
+**Usage:** `tool [COMMAND]`
+
+###### **Subcommands:**
+
+* [`remote`](tool/remote.md) — Manage remotes.
+* [`status`](tool/status.md) — Show status.
+
+"
+    );
+
+    assert_eq!(
+        pages["tool/remote.md"],
+        "\
+# `tool remote`
+
+Manage remotes.
+
+**Usage:** `tool remote [COMMAND]`
+
+###### **Subcommands:**
+
+* [`add`](remote/add.md) — Add a remote.
+
+"
+    );
+
+    assert_eq!(
+        pages["tool/remote/add.md"],
+        "\
+# `tool remote add`
+
+Add a remote.
+
+**Usage:** `tool remote add <name>`
+
+###### **Arguments:**
+
+* `<NAME>` (required)
+
+"
+    );
+}
+
+/// Test that the mdBook `SUMMARY.md` fragment links to the generated pages,
+/// and omits hidden commands.
+#[test]
+fn test_markdown_summary() {
+    let app = Command::new("tool")
+        .subcommand(
+            Command::new("remote")
+                .subcommand(Command::new("add"))
+                .subcommand(Command::new("secret").hide(true)),
+        )
+        .subcommand(Command::new("status"));
+
+    assert_eq!(
+        clap_markdown::help_markdown_command_summary(&app, ""),
+        "\
+- [tool](index.md)
+  - [remote](tool/remote.md)
+    - [add](tool/remote/add.md)
+  - [status](tool/status.md)
+"
+    );
+
+    assert_eq!(
+        clap_markdown::help_markdown_command_summary(&app, "reference/cli/"),
+        "\
+- [tool](reference/cli/index.md)
+  - [remote](reference/cli/tool/remote.md)
+    - [add](reference/cli/tool/remote/add.md)
+  - [status](reference/cli/tool/status.md)
+"
+    );
+
+    // Every page listed in the summary is generated, and vice versa.
+    let pages = clap_markdown::help_markdown_command_pages(
+        &app,
+        &MarkdownOptions::new(),
+    );
+
+    let summary = clap_markdown::help_markdown_command_summary(&app, "");
+    let summary_paths: Vec<&str> = summary
+        .lines()
+        .map(|line| line.rsplit_once('(').unwrap().1.trim_end_matches(')'))
+        .collect();
+
+    let mut sorted_summary_paths = summary_paths.clone();
+    sorted_summary_paths.sort();
+
+    assert_eq!(
+        sorted_summary_paths,
+        pages.keys().map(String::as_str).collect::<Vec<_>>()
+    );
+}
+
+/// Test the anchors generated for each `AnchorStyle`, including deduplication
+/// of command paths that slugify identically.
+#[test]
+fn test_anchor_styles() {
+    use clap_markdown::AnchorStyle;
+
+    let app = Command::new("Tool")
+        .subcommand(Command::new("my.cmd"))
+        .subcommand(Command::new("mycmd"))
+        .subcommand(Command::new("snake_case"))
+        .subcommand(Command::new("--weird"));
+
+    fn table_of_contents(markdown: &str) -> Vec<&str> {
+        markdown
+            .lines()
+            .skip_while(|line| *line != "**Command Overview:**")
+            .skip(2)
+            .take_while(|line| !line.is_empty())
+            .collect()
+    }
+
+    let markdown = |style: AnchorStyle| {
+        help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new().anchor_style(style),
+        )
+    };
+
+    assert_eq!(
+        table_of_contents(&markdown(AnchorStyle::GitHub)),
+        [
+            "* [`Tool`↴](#tool)",
+            "* [`Tool my.cmd`↴](#tool-mycmd)",
+            "* [`Tool mycmd`↴](#tool-mycmd-1)",
+            "* [`Tool snake_case`↴](#tool-snake_case)",
+            "* [`Tool --weird`↴](#tool---weird)",
+        ]
+    );
+
+    assert_eq!(
+        table_of_contents(&markdown(AnchorStyle::GitLab)),
+        [
+            "* [`Tool`↴](#tool)",
+            "* [`Tool my.cmd`↴](#tool-mycmd)",
+            "* [`Tool mycmd`↴](#tool-mycmd-1)",
+            "* [`Tool snake_case`↴](#tool-snake_case)",
+            "* [`Tool --weird`↴](#tool-weird)",
+        ]
+    );
+
+    assert_eq!(
+        table_of_contents(&markdown(AnchorStyle::MdBook)),
+        [
+            "* [`Tool`↴](#tool)",
+            "* [`Tool my.cmd`↴](#tool-mycmd)",
+            "* [`Tool mycmd`↴](#tool-mycmd-1)",
+            "* [`Tool snake_case`↴](#tool-snake_case)",
+            "* [`Tool --weird`↴](#tool---weird)",
+        ]
+    );
+
+    assert_eq!(
+        table_of_contents(&markdown(AnchorStyle::Custom(|text| {
+            text.replace(' ', "/")
+        }))),
+        [
+            "* [`Tool`↴](#Tool)",
+            "* [`Tool my.cmd`↴](#Tool/my.cmd)",
+            "* [`Tool mycmd`↴](#Tool/mycmd)",
+            "* [`Tool snake_case`↴](#Tool/snake_case)",
+            "* [`Tool --weird`↴](#Tool/--weird)",
+        ]
+    );
+
+    //------------------------------------------------
+    // Explicit anchors are written before each heading
+    //------------------------------------------------
+
+    let explicit = markdown(AnchorStyle::Explicit);
+
+    assert_eq!(
+        table_of_contents(&explicit)[1],
+        "* [`Tool my.cmd`↴](#Tool-my.cmd)"
+    );
+    assert!(explicit.contains("<a id=\"Tool-my.cmd\"></a>\n\n## `Tool my.cmd`"));
+    assert!(explicit.contains("<a id=\"Tool-mycmd\"></a>\n\n## `Tool mycmd`"));
+}
+
+/// Test that visible command and argument aliases are documented, and that
+/// hidden aliases are not.
+#[test]
+fn test_aliases() {
+    let app = Command::new("tool")
+        .subcommand(
+            Command::new("remove")
+                .about("Remove things.")
+                .visible_alias("rm")
+                .alias("delete")
+                .visible_aliases(["del", "erase"])
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .help("Do not ask for confirmation.")
+                        .action(clap::ArgAction::SetTrue)
+                        .visible_alias("yes")
+                        .visible_short_alias('y')
+                        .alias("no-prompt"),
+                ),
+        )
+        .subcommand(
+            Command::new("sync")
+                .about("Synchronize.")
+                .short_flag('S')
+                .visible_short_flag_alias('Y')
+                .visible_long_flag_alias("synchronize"),
+        )
+        .subcommand(Command::new("list").about("List things.").alias("ls"));
+
+    assert_eq!(
+        help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+                .show_footer(false)
+                .show_table_of_contents(false)
+        ),
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+## `tool`
+
+**Usage:** `tool [COMMAND]`
+
+###### **Subcommands:**
+
+* `remove` (aliases: `rm`, `del`, `erase`) — Remove things.
+* `sync` (aliases: `-Y`, `--synchronize`) — Synchronize.
+* `list` — List things.
+
+
+
+## `tool remove`
+
+Remove things.
+
+**Usage:** `tool remove [OPTIONS]`
+
+**Command Aliases:** `rm`, `del`, `erase`
+
+###### **Options:**
+
+* `--force` — Do not ask for confirmation.
+
+  Aliases: `-y`, `--yes`
+
+
+
+## `tool sync`
+
+Synchronize.
+
+**Usage:** `tool sync`
+
+**Command Aliases:** `-Y`, `--synchronize`
+
+
+
+## `tool list`
+
+List things.
+
+**Usage:** `tool list`
+
+
+
+"
+    );
+
+    let without_aliases = help_markdown_command_custom(
+        &app,
+        &MarkdownOptions::new().show_aliases(false),
+    );
+
+    assert!(!without_aliases.contains("`rm`"));
+    assert!(!without_aliases.contains("`--yes`"));
+}
+
+/// Test that environment variables bound to arguments are documented, both
+/// inline and in a consolidated section.
+#[test]
+#[cfg(feature = "env")]
+fn test_env_vars() {
+    use clap_markdown::EnvVarsSection;
+
+    let app = Command::new("tool")
+        .arg(
+            Arg::new("token")
+                .long("token")
+                .help("API token.")
+                .env("TOOL_TOKEN"),
+        )
+        .arg(
+            Arg::new("secret")
+                .long("secret")
+                .env("TOOL_SECRET")
+                .hide_env(true),
+        )
+        .subcommand(
+            Command::new("fetch")
+                .about("Fetch a URL.")
+                .arg(Arg::new("url").help("URL to fetch.").env("TOOL_URL"))
+                .arg(Arg::new("tok").short('t').env("TOOL_TOKEN")),
+        );
+
+    let markdown = |section: EnvVarsSection| {
+        help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+                .show_footer(false)
+                .show_table_of_contents(false)
+                .env_vars_section(section),
+        )
+    };
+
+    assert_eq!(
+        markdown(EnvVarsSection::PerCommand),
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+## `tool`
+
+**Usage:** `tool [OPTIONS] [COMMAND]`
+
+###### **Subcommands:**
+
+* `fetch` — Fetch a URL.
+
+###### **Options:**
+
+* `--token <TOKEN>` — API token.
+
+  Environment variable: `TOOL_TOKEN`
+* `--secret <SECRET>`
+
+###### **Environment Variables:**
+
+* `TOOL_TOKEN` — `--token`
+
+
+
+## `tool fetch`
+
+Fetch a URL.
+
+**Usage:** `tool fetch [OPTIONS] [url]`
+
+###### **Arguments:**
+
+* `[URL]` — URL to fetch.
+
+  Environment variable: `TOOL_URL`
+
+###### **Options:**
+
+* `-t <TOK>`
+
+  Environment variable: `TOOL_TOKEN`
+
+###### **Environment Variables:**
+
+* `TOOL_URL` — `<URL>`
+* `TOOL_TOKEN` — `-t`
+
+
+
+"
+    );
+
+    let document = markdown(EnvVarsSection::Document);
+
+    assert!(!document.contains("###### **Environment Variables:**"));
+    assert!(document.ends_with(
+        "\
+## Environment Variables
+
+* `TOOL_TOKEN` — `tool --token`, `tool fetch -t`
+* `TOOL_URL` — `tool fetch <URL>`
+
+
+
+"
+    ));
+
+    // Hidden environment variables are never shown.
+    assert!(!document.contains("TOOL_SECRET"));
+
+    let table = help_markdown_command_custom(
+        &app,
+        &MarkdownOptions::new().arg_layout(clap_markdown::ArgLayout::Table),
+    );
+
+    assert!(table
+        .contains("| `--token` | `<TOKEN>` |  | `TOOL_TOKEN` | API token. |"));
+    assert!(!table.contains("TOOL_SECRET"));
+}
+
+/// Test that arguments are grouped by their help heading, and ordered by their
+/// display order, matching clap's own help output.
+#[test]
+fn test_help_headings_and_display_order() {
+    let mut app = Command::new("tool")
+        .arg(Arg::new("input").help("Input file."))
+        .arg(Arg::new("verbose").short('v').help("Be verbose."))
+        .next_help_heading("Network")
+        .arg(Arg::new("port").long("port").help("Port to connect to."))
+        .arg(
+            Arg::new("host")
+                .long("host")
+                .help("Host to connect to.")
+                .display_order(0),
+        )
+        .next_help_heading("Output")
+        .arg(Arg::new("format").long("format").help("Output format."))
+        .arg(Arg::new("output").help("Output file."))
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .help("Use color.")
+                .hide_long_help(true),
+        )
+        .next_help_heading(None)
+        .arg(Arg::new("quiet").short('q').help("Be quiet."))
+        .next_help_heading("Network")
+        .arg(Arg::new("timeout").long("timeout").help("Timeout."));
+    let () = app.build();
+
+    assert_eq!(
+        app.render_long_help().to_string(),
+        "\
+Usage: tool [OPTIONS] [input] [output]
+
+Arguments:
+  [input]
+          Input file.
+
+Options:
+  -v <verbose>
+          Be verbose.
+
+  -q <quiet>
+          Be quiet.
+
+  -h, --help
+          Print help (see a summary with '-h')
+
+Network:
+      --host <host>
+          Host to connect to.
+
+      --port <port>
+          Port to connect to.
+
+      --timeout <timeout>
+          Timeout.
+
+Output:
+      --format <format>
+          Output format.
+
+  [output]
+          Output file.
+"
+    );
+
+    assert_eq!(
+        help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+                .show_footer(false)
+                .show_table_of_contents(false)
+        ),
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+## `tool`
+
+**Usage:** `tool [OPTIONS] [input] [output]`
+
+###### **Arguments:**
+
+* `[INPUT]` — Input file.
+
+###### **Options:**
+
+* `-v <VERBOSE>` — Be verbose.
+* `-q <QUIET>` — Be quiet.
+* `-h`, `--help` — Print help (see a summary with '-h')
+
+###### **Network:**
+
+* `--host <HOST>` — Host to connect to.
+* `--port <PORT>` — Port to connect to.
+* `--timeout <TIMEOUT>` — Timeout.
+
+###### **Output:**
+
+* `--format <FORMAT>` — Output format.
+* `[OUTPUT]` — Output file.
+
+
+
+"
+    );
+}
+
+/// Test that required arguments are marked, and that the number of values an
+/// argument takes is shown using the same notation as clap.
+#[test]
+fn test_required_and_arity() {
+    use clap::ArgAction;
+
+    let mut app = Command::new("tool")
+        .arg(Arg::new("files").required(true).num_args(1..))
+        .arg(Arg::new("config").long("config").required(true))
+        .arg(Arg::new("color").long("color").num_args(0..=1))
+        .arg(Arg::new("point").long("point").num_args(2))
+        .arg(Arg::new("jobs").short('j').require_equals(true))
+        .arg(
+            Arg::new("level")
+                .long("level")
+                .require_equals(true)
+                .num_args(0..=1),
+        )
+        .arg(Arg::new("include").short('I').action(ArgAction::Append))
+        .arg(Arg::new("verbose").short('v').action(ArgAction::Count))
+        .disable_help_flag(true);
+    let () = app.build();
+
+    assert_eq!(
+        app.render_long_help().to_string(),
+        "\
+Usage: tool [OPTIONS] --config <config> <files>...
+
+Arguments:
+  <files>...
+          
+
+Options:
+      --config <config>
+          
+
+      --color [<color>]
+          
+
+      --point <point> <point>
+          
+
+  -j=<jobs>
+          
+
+      --level[=<level>]
+          
+
+  -I <include>
+          
+
+  -v...
+"
+    );
+
+    assert_eq!(
+        help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+                .show_footer(false)
+                .show_table_of_contents(false)
+        ),
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+## `tool`
+
+**Usage:** `tool [OPTIONS] --config <config> <files>...`
+
+###### **Arguments:**
+
+* `<FILES>...` (required)
+
+###### **Options:**
+
+* `--config <CONFIG>` (required)
+* `--color [<COLOR>]`
+* `--point <POINT> <POINT>`
+* `-j=<JOBS>`
+* `--level[=<LEVEL>]`
+* `-I <INCLUDE>` (may be repeated)
+* `-v` (may be repeated)
+
+  Default value: `0`
+
+
+
+"
+    );
+}
+
+/// Test that every value name of an argument that takes several values is
+/// shown, and that value delimiters are indicated.
+#[test]
+fn test_multiple_value_names() {
+    use clap::ArgAction;
+
+    let mut app = Command::new("tool")
+        .arg(Arg::new("endpoints").value_names(["HOST", "PORT"]))
+        .arg(
+            Arg::new("range")
+                .long("range")
+                .value_names(["START", "END"]),
+        )
+        .arg(
+            Arg::new("rgb")
+                .long("rgb")
+                .value_names(["R", "G", "B"])
+                .num_args(1..=3),
+        )
+        .arg(
+            Arg::new("tags")
+                .long("tags")
+                .value_name("TAG")
+                .value_delimiter(',')
+                .action(ArgAction::Append),
+        )
+        .disable_help_flag(true);
+    let () = app.build();
+
+    assert_eq!(
+        app.render_usage().to_string(),
+        "Usage: tool [OPTIONS] [HOST] [PORT]"
+    );
+
+    assert_eq!(
+        help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+                .show_footer(false)
+                .show_table_of_contents(false)
+        ),
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+## `tool`
+
+**Usage:** `tool [OPTIONS] [HOST] [PORT]`
+
+###### **Arguments:**
+
+* `[HOST] [PORT]`
+
+###### **Options:**
+
+* `--range <START> <END>`
+* `--rgb <R> [G] [B]`
+* `--tags <TAG>[,<TAG>...]` (may be repeated)
+
+
+
+"
+    );
+}
+
+/// Test that conflicting, exclusive, and grouped arguments are documented.
+#[test]
+fn test_arg_relationships() {
+    use clap::{ArgAction, ArgGroup};
+
+    let app = Command::new("tool")
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("yaml"),
+        )
+        .arg(Arg::new("yaml").long("yaml").action(ArgAction::SetTrue))
+        .arg(
+            Arg::new("version-info")
+                .long("version-info")
+                .action(ArgAction::SetTrue)
+                .exclusive(true),
+        )
+        .arg(Arg::new("fast").long("fast").action(ArgAction::SetTrue))
+        .arg(Arg::new("slow").long("slow").action(ArgAction::SetTrue))
+        .group(ArgGroup::new("speed").args(["fast", "slow"]).required(true))
+        .disable_help_flag(true);
+
+    assert_eq!(
+        help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+                .show_footer(false)
+                .show_table_of_contents(false)
+        ),
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+## `tool`
+
+**Usage:** `tool [OPTIONS] <--fast|--slow>`
+
+###### **Options:**
+
+* `--json`
+
+  Cannot be used with `--yaml`.
+* `--yaml`
+
+  Cannot be used with `--json`.
+* `--version-info`
+
+  Cannot be used with any other arguments.
+* `--fast`
+
+  Cannot be used with `--slow`.
+* `--slow`
+
+  Cannot be used with `--fast`.
+
+###### **Argument Groups:**
+
+* `speed` — Exactly one of `--fast`, `--slow` must be given
+
+
+
+"
+    );
+}
+
+/// Test that subcommands link to the "Global Options" of their parents.
+#[test]
+fn test_global_args() {
+    use clap::ArgAction;
+
+    let app = Command::new("tool")
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Print more output"),
+        )
+        .arg(Arg::new("config").long("config").help("Config file"))
+        .subcommand(
+            Command::new("remote")
+                .arg(Arg::new("url").long("url"))
+                .subcommand(Command::new("add")),
+        )
+        .disable_help_flag(true)
+        .disable_help_subcommand(true);
+
+    assert_eq!(
+        help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+                .show_footer(false)
+                .show_table_of_contents(false)
+        ),
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+## `tool`
+
+**Usage:** `tool [OPTIONS] [COMMAND]`
+
+###### **Subcommands:**
+
+* `remote` — 
+
+###### **Options:**
+
+* `--config <CONFIG>` — Config file
+
+<a id=\"tool-global-options\"></a>
+
+###### **Global Options:**
+
+* `-v`, `--verbose` — Print more output
+
+
+
+## `tool remote`
+
+**Usage:** `tool remote [OPTIONS] [COMMAND]`
+
+###### **Subcommands:**
+
+* `add` — 
+
+###### **Options:**
+
+* `--url <URL>`
+
+This command also accepts the [global options](#tool-global-options) of `tool`.
+
+
+
+## `tool remote add`
+
+**Usage:** `tool remote add`
+
+This command also accepts the [global options](#tool-global-options) of `tool`.
+
+
+
+"
+    );
+
+    let pages = clap_markdown::help_markdown_command_pages(
+        &app,
+        &MarkdownOptions::new()
+            .show_footer(false)
+            .show_table_of_contents(false),
+    );
+
+    assert_eq!(
+        pages["tool/remote/add.md"],
+        "\
+# `tool remote add`
+
+**Usage:** `tool remote add`
+
+This command also accepts the [global options](../../index.md#global-options) of `tool`.
+
+"
+    );
+
+    // Global arguments propagated to subcommands by clap are still only
+    // documented once.
+    let mut app = app;
+    let () = app.build();
+
+    assert_eq!(
+        help_markdown_command_custom(&app, &MarkdownOptions::new())
+            .matches("`--verbose`")
+            .count(),
+        1
+    );
+
+    assert!(pages["index.md"].contains(
+        "<a id=\"global-options\"></a>\n\n###### **Global Options:**"
+    ));
+
+    // Global arguments with a custom help heading are not documented in a
+    // "Global Options" section, so link to the declaring command instead.
+    let app = Command::new("tool")
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .global(true)
+                .help_heading("Display"),
+        )
+        .subcommand(Command::new("run"));
+
+    let markdown = clap_markdown::help_markdown_command(&app);
+
+    assert!(!markdown.contains("global-options"));
+    assert!(markdown.contains(
+        "This command also accepts the [global options](#tool) of `tool`."
+    ));
+}
+
+/// Test that styled help text is converted to Markdown emphasis and code.
+#[test]
+#[cfg(feature = "color")]
+fn test_styled_help() {
+    use clap::builder::{
+        styling::{AnsiColor, Style},
+        StyledStr, Styles,
+    };
+
+    let literal = AnsiColor::Green.on_default().bold();
+    let bold = Style::new().bold();
+    let underline = Style::new().underline();
+
+    let help = StyledStr::from(format!(
+        "Use {literal}--color=never{literal:#} to {bold}disable{bold:#} \
+         {underline}all colors{underline:#}."
+    ));
+
+    let app = Command::new("tool")
+        .styles(Styles::styled().literal(literal))
+        .about(help.clone())
+        .arg(Arg::new("color").long("color").help(help))
+        .disable_help_flag(true);
+
+    assert_eq!(
+        help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+                .show_footer(false)
+                .show_table_of_contents(false)
+        ),
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+## `tool`
+
+Use `--color=never` to **disable** _all colors_.
+
+**Usage:** `tool [OPTIONS]`
+
+###### **Options:**
+
+* `--color <COLOR>` — Use `--color=never` to **disable** _all colors_.
+
+
+
+"
+    );
+}
+
+/// Test that, without the `color` feature, styled help text is shown as plain
+/// text when clap's `color` feature is enabled (as it is for these tests).
+#[test]
+#[cfg(not(feature = "color"))]
+fn test_styled_help_without_color() {
+    use clap::builder::{styling::AnsiColor, StyledStr, Styles};
+
+    let literal = AnsiColor::Green.on_default().bold();
+
+    let app = Command::new("tool")
+        .styles(Styles::styled().literal(literal))
+        .about(StyledStr::from(format!(
+            "Use {literal}--color=never{literal:#} to disable colors."
+        )));
+
+    let markdown = clap_markdown::help_markdown_command(&app);
+
+    assert!(markdown.contains("\n\nUse --color=never to disable colors.\n\n"));
+    assert!(!markdown.contains('\x1b'));
+}
+
+/// Test that help text is not shown as literal text when the literal style is
+/// unstyled, as it is with `Styles::plain()`.
+#[test]
+fn test_plain_styles() {
+    use clap::builder::Styles;
+
+    let app = Command::new("tool")
+        .styles(Styles::plain())
+        .about("Plain about text")
+        .arg(Arg::new("opt").long("opt").help("Some help"))
+        .disable_help_flag(true);
+
+    assert_eq!(
+        help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+                .show_footer(false)
+                .show_table_of_contents(false)
+        ),
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+## `tool`
+
+Plain about text
+
+**Usage:** `tool [OPTIONS]`
+
+###### **Options:**
+
+* `--opt <OPT>` — Some help
+
+
+
+"
+    );
+}
+
+/// Test that plain text help is escaped, and Markdown help is left unchanged.
+#[test]
+fn test_help_text_format() {
+    use clap::builder::PossibleValue;
+    use clap_markdown::HelpTextFormat;
+
+    let app = Command::new("tool")
+        .about("Matches *.rs files in <dir>")
+        .long_about(
+            "Matches *.rs files in <dir>.\n\n\
+             # of files is limited\n\
+             1. first\n\
+             - second",
+        )
+        .arg(
+            Arg::new("mode")
+                .long("mode")
+                .help("Use <name> here, e.g. `fast` or **slow**")
+                .value_parser([
+                    PossibleValue::new("a_b").help("Uses [brackets] & more")
+                ]),
+        )
+        .disable_help_flag(true);
+
+    let plain = help_markdown_command_custom(
+        &app,
+        &MarkdownOptions::new()
+            .show_footer(false)
+            .show_table_of_contents(false)
+            .help_text_format(HelpTextFormat::PlainText),
+    );
+
+    assert_eq!(
+        plain,
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+## `tool`
+
+Matches \\*.rs files in \\<dir\\>.
+
+\\# of files is limited
+1\\. first
+\\- second
+
+**Usage:** `tool [OPTIONS]`
+
+###### **Options:**
+
+* `--mode <MODE>` — Use \\<name\\> here, e.g. \\`fast\\` or \\*\\*slow\\*\\*
+
+  Possible values:
+  - `a_b`:
+    Uses \\[brackets\\] \\& more
+
+
+
+
+"
+    );
+
+    // By default, help text is assumed to already be Markdown.
+    let markdown = help_markdown_command_custom(
+        &app,
+        &MarkdownOptions::new()
+            .show_footer(false)
+            .show_table_of_contents(false),
+    );
+
+    assert!(markdown.contains(
+        "* `--mode <MODE>` — Use <name> here, e.g. `fast` or **slow**\n"
+    ));
+    assert!(markdown.contains("# of files is limited\n1. first\n- second\n"));
+}
+
+/// Test that a custom `Renderer` can override only some of the hooks.
+#[test]
+fn test_custom_renderer() {
+    use std::fmt;
+
+    use clap_markdown::{CommandContext, Renderer, TocEntry};
+
+    // Overrides a few hooks, and relies on the defaults for the rest.
+    struct Custom;
+
+    impl Renderer for Custom {
+        fn write_table_of_contents_entry(
+            &self,
+            out: &mut dyn fmt::Write,
+            _options: &MarkdownOptions,
+            entry: &TocEntry,
+        ) -> fmt::Result {
+            writeln!(
+                out,
+                "- {} ({})",
+                entry.command_path().join(" "),
+                entry.href()
+            )
+        }
+
+        fn write_usage(
+            &self,
+            out: &mut dyn fmt::Write,
+            cx: &CommandContext,
+        ) -> fmt::Result {
+            writeln!(out, "```\n$ {}\n```\n", cx.command_path().join(" "))
+        }
+
+        fn write_arg(
+            &self,
+            out: &mut dyn fmt::Write,
+            _cx: &CommandContext,
+            arg: &Arg,
+        ) -> fmt::Result {
+            writeln!(out, "* {}", arg.get_id())
+        }
+
+        fn write_footer(
+            &self,
+            out: &mut dyn fmt::Write,
+            _options: &MarkdownOptions,
+            command: &Command,
+        ) -> fmt::Result {
+            writeln!(out, "Generated for {}.", command.get_name())
+        }
+    }
+
+    let app = Command::new("tool")
+        .arg(Arg::new("verbose").long("verbose").help("Print more"))
+        .subcommand(Command::new("run").about("Run it."))
+        .disable_help_flag(true)
+        .disable_help_subcommand(true);
+
+    assert_eq!(
+        help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new().renderer(Custom)
+        ),
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+**Command Overview:**
+
+- tool (#tool)
+- tool run (#tool-run)
+
+## `tool`
+
+```
+$ tool
+```
+
+###### **Subcommands:**
+
+* `run` — Run it.
+
+###### **Options:**
+
+* verbose
+
+
+
+## `tool run`
+
+Run it.
+
+```
+$ tool run
+```
+
+
+
+Generated for tool.
+"
+    );
+}
+
+/// Test documenting arguments as Markdown tables.
+#[test]
+fn test_arg_table_layout() {
+    use clap::builder::PossibleValue;
+    use clap_markdown::ArgLayout;
+
+    let app = Command::new("tool")
+        .arg(Arg::new("file").required(true).help("The file to read"))
+        .arg(
+            Arg::new("config")
+                .short('c')
+                .long("config")
+                .value_name("FILE")
+                .default_value("tool.toml")
+                .help("Path to the config")
+                .long_help("Path to the config.\n\nUse `a|b` to\nchoose both."),
+        )
+        .arg(
+            Arg::new("mode")
+                .long("mode")
+                .visible_alias("kind")
+                .value_parser([
+                    PossibleValue::new("fast").help("Go fast"),
+                    PossibleValue::new("slow"),
+                ]),
+        )
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .value_parser(["auto", "never"])
+                .conflicts_with("mode"),
+        )
+        .disable_help_flag(true);
+
+    assert_eq!(
+        help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+                .show_footer(false)
+                .show_table_of_contents(false)
+                .arg_layout(ArgLayout::Table),
+        ),
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+## `tool`
+
+**Usage:** `tool [OPTIONS] <file>`
+
+###### **Arguments:**
+
+| Flag | Value | Default | Env | Description |
+|------|-------|---------|-----|-------------|
+| `<FILE>` (required) |  |  |  | The file to read |
+
+###### **Options:**
+
+| Flag | Value | Default | Env | Description |
+|------|-------|---------|-----|-------------|
+| `-c`, `--config` | `<FILE>` | `tool.toml` |  | Path to the config.<br><br>Use `a\\|b` to<br>choose both. |
+| `--mode` | `<MODE>` |  |  | Alias: `--kind`<br><br>Cannot be used with `--color`.<br><br>Possible values:<br>- `fast`: Go fast<br>- `slow` |
+| `--color` | `<COLOR>` |  |  | Cannot be used with `--mode`.<br><br>Possible values: `auto`, `never` |
+
+
+
+"
+    );
+}
+
+/// Test that pipes in table cells are escaped exactly once, for both help text
+/// formats.
+#[test]
+fn test_arg_table_layout_pipes() {
+    use clap_markdown::{ArgLayout, HelpTextFormat};
+
+    let app = Command::new("tool")
+        .arg(Arg::new("pick").long("pick").help("Pick a|b"))
+        .disable_help_flag(true);
+
+    for format in [HelpTextFormat::Markdown, HelpTextFormat::PlainText] {
+        let markdown = help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+                .arg_layout(ArgLayout::Table)
+                .help_text_format(format),
+        );
+
+        assert!(
+            markdown.contains("| `--pick` | `<PICK>` |  |  | Pick a\\|b |\n"),
+            "{format:?}: {markdown}"
+        );
+    }
+}
+
+/// Test replacing the default layout of the document with templates.
+#[test]
+fn test_templates() {
+    let app = Command::new("tool")
+        .version("1.2.3")
+        .about("Does things.")
+        .arg(Arg::new("verbose").long("verbose").help("Print more"))
+        .subcommand(Command::new("run").about("Run it.").visible_alias("r"))
+        .disable_help_flag(true)
+        .disable_version_flag(true)
+        .disable_help_subcommand(true);
+
+    assert_eq!(
+        help_markdown_command_custom(
+            &app,
+            &MarkdownOptions::new()
+                .header_template(
+                    "# {{name}} {{version}}\n\n{{about}}\n\n\
+                     ## Contents\n\n{{table_of_contents}}"
+                        .to_owned()
+                )
+                .command_template(
+                    "{{heading}}\n\n\
+                     ```console\n$ {{usage}}\n```\n\n\
+                     Aliases: {{aliases}}\n\n\
+                     {{subcommands}}\n\n{{arguments}}"
+                        .to_owned()
+                )
+                .section_heading_template("#### {{heading}}".to_owned())
+                .footer_template(
+                    "_{{name}} {{ version }} — do not edit_".to_owned()
+                ),
+        ),
+        "\
+# tool 1.2.3
+
+Does things.
+
+## Contents
+
+* [`tool`↴](#tool)
+* [`tool run`↴](#tool-run)
+
+## `tool`
+
+```console
+$ tool [OPTIONS] [COMMAND]
+```
+
+Aliases: 
+
+#### Subcommands
+
+* `run` (alias: `r`) — Run it.
+
+#### Options
+
+* `--verbose <VERBOSE>` — Print more
+
+
+
+## `tool run`
+
+```console
+$ tool run
+```
+
+Aliases: `r`
+
+
+
+_tool 1.2.3 — do not edit_
+"
+    );
+}
+
+/// Test that the `{{heading}}` of a command template uses the `Renderer`.
+#[test]
+fn test_command_template_renderer() {
+    use std::fmt;
+
+    use clap_markdown::{CommandContext, Renderer};
+
+    struct Headings;
+
+    impl Renderer for Headings {
+        fn write_command_heading(
+            &self,
+            out: &mut dyn fmt::Write,
+            cx: &CommandContext,
+        ) -> fmt::Result {
+            writeln!(out, "## {}\n", cx.command_path().join(" "))
+        }
+    }
+
+    let app = Command::new("tool")
+        .subcommand(Command::new("run"))
+        .disable_help_flag(true)
+        .disable_help_subcommand(true);
+
+    // The template uses the same heading hook as the default layout.
+    let markdown = help_markdown_command_custom(
+        &app,
+        &MarkdownOptions::new()
+            .show_table_of_contents(false)
+            .show_footer(false)
+            .anchor_style(clap_markdown::AnchorStyle::Explicit)
+            .command_template("{{heading}}\n\n{{usage}}".to_owned())
+            .renderer(Headings),
+    );
+
+    assert!(markdown.contains("## tool\n\ntool [COMMAND]\n"));
+    assert!(markdown.contains("## tool run\n\ntool run\n"));
+    assert!(!markdown.contains("<a id="));
+}
+
+/// Test adding content around the default header and footer.
+#[test]
+fn test_custom_header_and_footer() {
+    use clap_markdown::help_markdown_command_pages;
+
+    let app = Command::new("tool")
+        .version("1.2.3")
+        .subcommand(Command::new("run"))
+        .disable_help_flag(true)
+        .disable_version_flag(true)
+        .disable_help_subcommand(true);
+
+    let options = MarkdownOptions::new()
+        .show_table_of_contents(false)
+        .header_template(
+            "> Generated from `{{name}}` v{{version}} on {{date}} — do not edit.\n\n\
+             {{default_header}}"
+                .to_owned(),
+        )
+        .footer_template(
+            "{{default_footer}}\n\nLicensed under the MIT license.\n".to_owned(),
+        )
+        .date("2024-06-15".to_owned());
+
+    assert_eq!(
+        help_markdown_command_custom(&app, &options),
+        "\
+> Generated from `tool` v1.2.3 on 2024-06-15 — do not edit.
+
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+## `tool`
+
+**Usage:** `tool [COMMAND]`
+
+###### **Subcommands:**
+
+* `run` — 
+
+
+
+## `tool run`
+
+**Usage:** `tool run`
+
+
+
+<hr/>
+
+<small><i>
+    This document was generated automatically by
+    <a href=\"https://crates.io/crates/clap-markdown\"><code>clap-markdown</code></a>.
+</i></small>
+
+Licensed under the MIT license.
+"
+    );
+
+    // Every page has the footer, which refers to the root command.
+    let pages = help_markdown_command_pages(
+        &app,
+        &options.footer_template("Licensed under {{name}}.".to_owned()),
+    );
+
+    assert_eq!(
+        pages["tool/run.md"],
+        "\
+# `tool run`
+
+**Usage:** `tool run`
+
+Licensed under tool.
+"
+    );
+    assert!(pages["index.md"].starts_with("> Generated from `tool` v1.2.3"));
+}
+
+/// Test the YAML front matter of a single document and of each page.
+#[test]
+fn test_front_matter() {
+    use clap_markdown::{help_markdown_command_pages, DEFAULT_FRONT_MATTER};
+
+    let app = Command::new("tool")
+        .version("1.2.3")
+        .about("A tool: it does \"things\".")
+        .subcommand(
+            Command::new("remote")
+                .about("Manage remotes.")
+                .subcommand(Command::new("add").about("Add a remote.")),
+        )
+        .subcommand(Command::new("run"))
+        .disable_help_flag(true)
+        .disable_version_flag(true)
+        .disable_help_subcommand(true);
+
+    let options = MarkdownOptions::new()
+        .show_footer(false)
+        .show_table_of_contents(false)
+        .front_matter(DEFAULT_FRONT_MATTER.to_owned());
+
+    assert!(help_markdown_command_custom(&app, &options).starts_with(
+        "\
+---
+title: \"tool\"
+description: \"A tool: it does \\\"things\\\".\"
+weight: 1
+---
+
+# Command-Line Help for `tool`
+"
+    ));
+
+    let pages = help_markdown_command_pages(
+        &app,
+        &options.front_matter(
+            "title: {{title}}\nversion: {{version}}\nweight: {{weight}}\n\
+             slug: {{slug}}\n"
+                .to_owned(),
+        ),
+    );
+
+    assert_eq!(
+        pages["tool/remote/add.md"],
+        "\
+---
+title: \"tool remote add\"
+version: \"1.2.3\"
+weight: 3
+slug: \"tool/remote/add\"
+---
+
+# `tool remote add`
+
+Add a remote.
+
+**Usage:** `tool remote add`
+
+"
+    );
+    assert!(pages["tool/run.md"].contains("\nweight: 4\n"));
+    assert!(pages["index.md"].contains("\nslug: \"index\"\n"));
+}
+
+/// Test that the front matter description is plain text, without the ANSI
+/// escape sequences of styled help text.
+#[test]
+fn test_front_matter_styled_about() {
+    use clap::builder::{styling::Style, StyledStr};
+
+    let bold = Style::new().bold();
+
+    let app = Command::new("tool")
+        .about(StyledStr::from(format!("Use {bold}bold{bold:#} text")))
+        .disable_help_flag(true);
+
+    let markdown = help_markdown_command_custom(
+        &app,
+        &MarkdownOptions::new()
+            .front_matter("description: {{about}}".to_owned()),
+    );
+
+    assert!(markdown.starts_with("---\ndescription: \"Use bold text\"\n---\n"));
+}
+
+/// Test that the MDX flavor escapes help text and generates JSX-safe HTML.
+#[test]
+fn test_mdx_flavor() {
+    use clap_markdown::{ArgLayout, MarkdownFlavor};
+
+    let app = Command::new("tool")
+        .about("Reads {config} from <dir>, e.g. `<dir>/{a,b}`")
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .value_name("FILE")
+                .help("Path to <FILE>")
+                .long_help("Path to <FILE>.\n\nDefaults to {home}."),
+        )
+        .disable_help_flag(true);
+
+    let options = MarkdownOptions::new()
+        .show_table_of_contents(false)
+        .flavor(MarkdownFlavor::Mdx)
+        .arg_admonition("note".to_owned());
+
+    assert_eq!(
+        help_markdown_command_custom(&app, &options),
+        "\
+# Command-Line Help for `tool`
+
+This document contains the help content for the `tool` command-line program.
+
+## `tool`
+
+Reads \\{config\\} from \\<dir>, e.g. `<dir>/{a,b}`
+
+**Usage:** `tool [OPTIONS]`
+
+###### **Options:**
+
+:::note
+
+* `--config <FILE>` — Path to \\<FILE>.
+
+   Defaults to \\{home\\}.
+
+:::
+
+
+
+<hr/>
+
+<small><i>This document was generated automatically by <a href=\"https://crates.io/crates/clap-markdown\"><code>clap-markdown</code></a>.</i></small>
+"
+    );
+
+    // Line breaks in table cells are self-closing.
+    assert!(help_markdown_command_custom(
+        &app,
+        &options.arg_layout(ArgLayout::Table)
+    )
+    .contains(
+        "| `--config` | `<FILE>` |  |  | Path to \\<FILE>.<br/><br/>Defaults to \\{home\\}. |\n"
+    ));
+}
+
+/// Test that text MDX would parse as JSX or JavaScript is escaped outside of
+/// code spans everywhere in the document, not just in help text.
+#[test]
+fn test_mdx_escaping() {
+    use clap_markdown::{ArgLayout, MarkdownFlavor};
+
+    let app = Command::new("tool")
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FMT")
+                .default_value("{name}")
+                .help_heading("Options <advanced>")
+                .help("Output {format}, e.g. `{name}-<n>`"),
+        )
+        .disable_help_flag(true);
+
+    let options = MarkdownOptions::new()
+        .title("Help for {tool} <cli>".to_owned())
+        .show_table_of_contents(false)
+        .show_footer(false)
+        .flavor(MarkdownFlavor::Mdx);
+
+    let markdown = help_markdown_command_custom(&app, &options);
+
+    assert!(markdown.starts_with("# Help for \\{tool\\} \\<cli>\n"));
+    assert!(markdown.contains("\n###### **Options \\<advanced>:**\n"));
+    assert!(markdown.contains(
+        "* `--format <FMT>` — Output \\{format\\}, e.g. `{name}-<n>`\n\n  \
+         Default value: `{name}`\n"
+    ));
+
+    // Literal values in table cells are code spans, so are left unchanged.
+    let options = options.arg_layout(ArgLayout::Table);
+    let markdown = help_markdown_command_custom(&app, &options);
+
+    assert!(markdown.contains("\n###### **Options \\<advanced>:**\n"));
+    assert!(markdown.contains(
+        "| `--format` | `<FMT>` | `{name}` |  | Output \\{format\\}, e.g. \
+         `{name}-<n>` |\n"
+    ));
+
+    // Standard Markdown is left unchanged.
+    let markdown = help_markdown_command_custom(
+        &app,
+        &options.flavor(MarkdownFlavor::Standard),
+    );
+
+    assert!(markdown.starts_with("# Help for {tool} <cli>\n"));
+    assert!(markdown.contains("\n###### **Options <advanced>:**\n"));
+}
@@ -0,0 +1,208 @@
+use clap::{Arg, ArgAction, ArgGroup, Command};
+use clap_markdown::help_model_command;
+
+use pretty_assertions::assert_eq;
+
+fn app() -> Command {
+    Command::new("tool")
+        .about("A tool.")
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Print more output"),
+        )
+        .subcommand(
+            Command::new("remote")
+                .about("Manage remotes.")
+                .visible_alias("r")
+                .arg(Arg::new("name").required(true).help("Name of the remote"))
+                .arg(
+                    Arg::new("mode")
+                        .long("mode")
+                        .value_parser(["fetch", "push"])
+                        .default_value("fetch"),
+                )
+                .arg(Arg::new("url").long("url").conflicts_with("mode"))
+                .arg(Arg::new("secret").long("secret").hide(true))
+                .group(
+                    ArgGroup::new("target")
+                        .args(["mode", "url"])
+                        .required(true),
+                ),
+        )
+        .subcommand(Command::new("secret").hide(true))
+        .disable_help_flag(true)
+        .disable_version_flag(true)
+        .disable_help_subcommand(true)
+}
+
+/// Test the documentation model of a command and its visible subcommands.
+#[test]
+fn test_model() {
+    let model = help_model_command(&app());
+
+    assert_eq!(model.name, "tool");
+    assert_eq!(model.path, ["tool"]);
+    assert_eq!(model.about.as_deref(), Some("A tool."));
+    assert_eq!(model.global_args_from, Vec::<Vec<String>>::new());
+
+    let headings: Vec<&str> = model
+        .arg_sections
+        .iter()
+        .map(|section| section.heading.as_str())
+        .collect();
+    assert_eq!(headings, ["Global Options"]);
+
+    let verbose = &model.arg_sections[0].args[0];
+    assert_eq!(verbose.display_name, "--verbose");
+    assert_eq!(verbose.short, Some('v'));
+    assert!(verbose.global);
+    assert_eq!(verbose.help.as_deref(), Some("Print more output"));
+
+    // Hidden subcommands are omitted.
+    assert_eq!(model.subcommands.len(), 1);
+
+    let remote = &model.subcommands[0];
+    assert_eq!(remote.path, ["tool", "remote"]);
+    assert_eq!(
+        remote.usage,
+        "tool remote <--mode <mode>|--url <url>> <name>"
+    );
+    assert_eq!(remote.aliases, ["r"]);
+    assert_eq!(remote.global_args_from, [["tool"]]);
+
+    // Hidden and inherited global arguments are omitted.
+    let args: Vec<(&str, &str)> = remote
+        .arg_sections
+        .iter()
+        .flat_map(|section| {
+            section.args.iter().map(|arg| {
+                (section.heading.as_str(), arg.display_name.as_str())
+            })
+        })
+        .collect();
+    assert_eq!(
+        args,
+        [
+            ("Arguments", "<NAME>"),
+            ("Options", "--mode"),
+            ("Options", "--url")
+        ]
+    );
+
+    let name = &remote.arg_sections[0].args[0];
+    assert!(name.positional);
+    assert!(name.required);
+
+    let mode = &remote.arg_sections[1].args[0];
+    assert_eq!(mode.default_values, ["fetch"]);
+    assert_eq!(
+        mode.possible_values
+            .iter()
+            .map(|value| value.name.as_str())
+            .collect::<Vec<_>>(),
+        ["fetch", "push"]
+    );
+    assert_eq!(mode.conflicts_with, ["--url"]);
+
+    let group = &remote.arg_groups[0];
+    assert_eq!(group.id, "target");
+    assert!(group.required);
+    assert!(!group.multiple);
+    assert_eq!(group.args, ["--mode", "--url"]);
+}
+
+/// Test that styled help text is included without its ANSI escape sequences.
+#[test]
+fn test_model_styled_help() {
+    use clap::builder::{styling::Style, StyledStr};
+
+    let bold = Style::new().bold();
+    let help = StyledStr::from(format!("Use {bold}bold{bold:#} text"));
+
+    let app = Command::new("tool")
+        .about(help.clone())
+        .arg(
+            Arg::new("mode")
+                .long("mode")
+                .help(help.clone())
+                .value_parser([clap::builder::PossibleValue::new("fast")
+                    .help(help.clone())]),
+        )
+        .disable_help_flag(true);
+
+    let model = help_model_command(&app);
+
+    assert_eq!(model.about.as_deref(), Some("Use bold text"));
+
+    let mode = &model.arg_sections[0].args[0];
+
+    assert_eq!(mode.help.as_deref(), Some("Use bold text"));
+    assert_eq!(
+        mode.possible_values[0].help.as_deref(),
+        Some("Use bold text")
+    );
+}
+
+/// Test exporting the documentation model as JSON.
+#[cfg(feature = "serde")]
+#[test]
+fn test_json() {
+    let json = clap_markdown::help_json_command(
+        &Command::new("tool")
+            .arg(Arg::new("file").help("The file").value_parser(["a.txt"])),
+    );
+
+    assert_eq!(
+        json,
+        r#"{
+  "name": "tool",
+  "path": [
+    "tool"
+  ],
+  "usage": "tool [file]",
+  "about": null,
+  "long_about": null,
+  "before_help": null,
+  "after_help": null,
+  "aliases": [],
+  "arg_sections": [
+    {
+      "heading": "Arguments",
+      "args": [
+        {
+          "id": "file",
+          "display_name": "<FILE>",
+          "short": null,
+          "long": null,
+          "value_notation": "[FILE]",
+          "positional": true,
+          "required": false,
+          "repeatable": false,
+          "global": false,
+          "exclusive": false,
+          "help": "The file",
+          "long_help": null,
+          "aliases": [],
+          "conflicts_with": [],
+          "env": null,
+          "default_values": [],
+          "possible_values": [
+            {
+              "name": "a.txt",
+              "help": null
+            }
+          ]
+        }
+      ]
+    }
+  ],
+  "global_args_from": [],
+  "arg_groups": [],
+  "subcommands": []
+}"#
+    );
+}